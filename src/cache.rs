@@ -0,0 +1,420 @@
+use crate::Data;
+use crate::Error;
+use crate::dates::week_start;
+use crate::dates::weekday_name;
+use crate::locale::Locale;
+use crate::menu::Menu;
+use crate::menu::Week;
+use crate::menu::WeekDay;
+use crate::provider::RestaurantId;
+use chrono::DateTime;
+use chrono::Datelike;
+use chrono::Days;
+use chrono::Local;
+use chrono::NaiveDate;
+use chrono::Utc;
+use chrono_tz::Europe::Helsinki;
+use std::collections::HashMap;
+use std::env;
+
+/// Default time to live for cached menus in seconds, override with `$MENU_CACHE_TTL`
+const DEFAULT_TTL: i64 = 30 * 60;
+
+/// Entries not refreshed in this many seconds are dropped entirely
+const MAX_AGE: i64 = 7 * 24 * 60 * 60;
+
+#[derive(Debug, Clone)]
+pub struct CachedMenu {
+    pub menu: Menu,
+    /// Unix timestamp of when the menu was downloaded
+    pub fetched_at: i64,
+}
+
+/// A menu from [`cached_day`], `outdated` is set when the caterer could not be
+/// reached and an expired copy was used instead
+#[derive(Debug, Clone)]
+pub struct FetchedMenu {
+    pub menu: Menu,
+    pub outdated: Option<i64>,
+}
+
+impl FetchedMenu {
+    /// Notice to show alongside an outdated menu
+    pub fn outdated_note(&self, locale: Locale) -> Option<String> {
+        let fetched_at = DateTime::from_timestamp(self.outdated?, 0)?.with_timezone(&Helsinki);
+
+        Some(format!(
+            "{} {}",
+            locale.strings().outdated,
+            fetched_at.format("%d.%m. %H:%M")
+        ))
+    }
+}
+
+/// A caterer's weekly menu, only the current week is ever fetched whole
+#[derive(Debug, Clone)]
+pub struct CachedWeek {
+    pub week: Week,
+    /// Unix timestamp of when the week was downloaded
+    pub fetched_at: i64,
+}
+
+#[derive(Debug)]
+pub struct MenuCache {
+    ttl: i64,
+    entries: HashMap<(RestaurantId, String), CachedMenu>,
+    weeks: HashMap<RestaurantId, CachedWeek>,
+}
+
+impl MenuCache {
+    pub fn new(ttl: i64) -> Self {
+        MenuCache {
+            ttl,
+            entries: HashMap::new(),
+            weeks: HashMap::new(),
+        }
+    }
+
+    pub fn from_env() -> Result<Self, Error> {
+        let ttl = match env::var("MENU_CACHE_TTL") {
+            Ok(ttl) => ttl.parse()?,
+            Err(_) => DEFAULT_TTL,
+        };
+
+        Ok(Self::new(ttl))
+    }
+
+    /// Returns the menu only if it was fetched within the ttl
+    pub fn get_fresh(&self, restaurant: &RestaurantId, day: &str, now: i64) -> Option<&CachedMenu> {
+        self.entries
+            .get(&(restaurant.clone(), day.to_string()))
+            .filter(|c| now - c.fetched_at < self.ttl)
+    }
+
+    /// Returns the menu no matter how old it is
+    pub fn get_any(&self, restaurant: &RestaurantId, day: &str) -> Option<&CachedMenu> {
+        self.entries.get(&(restaurant.clone(), day.to_string()))
+    }
+
+    /// Returns the week starting at `monday` only if it was fetched within the ttl
+    pub fn get_fresh_week(
+        &self,
+        restaurant: &RestaurantId,
+        monday: NaiveDate,
+        now: i64,
+    ) -> Option<&Week> {
+        self.weeks
+            .get(restaurant)
+            .filter(|c| c.week.start == monday && now - c.fetched_at < self.ttl)
+            .map(|c| &c.week)
+    }
+
+    /// Returns the week starting at `monday` no matter how old it is
+    pub fn get_any_week(&self, restaurant: &RestaurantId, monday: NaiveDate) -> Option<&Week> {
+        self.weeks
+            .get(restaurant)
+            .map(|c| &c.week)
+            .filter(|w| w.start == monday)
+    }
+
+    /// Caches the week and each of its days, the info buttons of the week's
+    /// messages will need those
+    pub fn insert_week(&mut self, restaurant: &RestaurantId, week: Week, now: i64) {
+        self.weeks.retain(|_, c| now - c.fetched_at < MAX_AGE);
+
+        for day in &week.days {
+            let date = day.date.format("%Y-%m-%d").to_string();
+            self.insert(restaurant, &date, day.menu.clone(), now);
+        }
+
+        self.weeks.insert(
+            restaurant.clone(),
+            CachedWeek {
+                week,
+                fetched_at: now,
+            },
+        );
+    }
+
+    /// The next `n` days from `from` on that have food, with their menus if
+    /// cached. Days that aren't cached are expected to have food on weekdays.
+    pub fn serving_days(
+        &self,
+        restaurant: &RestaurantId,
+        from: NaiveDate,
+        n: usize,
+    ) -> Vec<(NaiveDate, Option<&Menu>)> {
+        from.iter_days()
+            // a closed restaurant doesn't need to be looked for forever
+            .take(n * 2 + 7)
+            .filter_map(|date| {
+                let menu = self.get_any(restaurant, &date.format("%Y-%m-%d").to_string());
+
+                match menu {
+                    Some(cached) if cached.menu.dishes.is_empty() => None,
+                    Some(cached) => Some((date, Some(&cached.menu))),
+                    None if date.weekday().number_from_monday() > 5 => None,
+                    None => Some((date, None)),
+                }
+            })
+            .take(n)
+            .collect()
+    }
+
+    /// A menu generated before the one already cached only renews the cached
+    /// one, so a lagging response can never replace newer data
+    pub fn insert(
+        &mut self,
+        restaurant: &RestaurantId,
+        day: &str,
+        menu: Menu,
+        now: i64,
+    ) -> &CachedMenu {
+        self.entries.retain(|_, c| now - c.fetched_at < MAX_AGE);
+
+        let cached = self
+            .entries
+            .entry((restaurant.clone(), day.to_string()))
+            .or_insert_with(|| CachedMenu {
+                menu: menu.clone(),
+                fetched_at: now,
+            });
+
+        if cached.menu.generated_timestamp <= menu.generated_timestamp {
+            cached.menu = menu;
+        }
+        cached.fetched_at = now;
+
+        cached
+    }
+}
+
+/// [`RestaurantId::fetch_day`] through the cache in [`Data`], falls back to an expired copy
+/// if fetching fails
+pub async fn cached_day(
+    data: &Data,
+    restaurant: &RestaurantId,
+    day: &str,
+) -> Result<FetchedMenu, Error> {
+    let now = Utc::now().timestamp();
+
+    if let Some(cached) = data.cache.lock().await.get_fresh(restaurant, day, now) {
+        return Ok(FetchedMenu {
+            menu: cached.menu.clone(),
+            outdated: None,
+        });
+    }
+
+    match restaurant.fetch_day(day).await {
+        Ok(menu) => {
+            // the cache might have kept a newer menu
+            let mut cache = data.cache.lock().await;
+            let cached = cache.insert(restaurant, day, menu, now);
+
+            Ok(FetchedMenu {
+                menu: cached.menu.clone(),
+                outdated: None,
+            })
+        }
+        Err(e) => {
+            println!("Error fetching menu {restaurant} {day}: {e:?}");
+
+            let cache = data.cache.lock().await;
+            let cached = cache.get_any(restaurant, day).ok_or(e)?;
+
+            Ok(FetchedMenu {
+                menu: cached.menu.clone(),
+                outdated: Some(cached.fetched_at),
+            })
+        }
+    }
+}
+
+/// The week starting at `monday`. Sodexo's weekly menu only has the current
+/// week, other weeks are put together from daily menus.
+pub async fn cached_week(
+    data: &Data,
+    restaurant: &RestaurantId,
+    monday: NaiveDate,
+) -> Result<Week, Error> {
+    if monday == week_start(Local::now().date_naive()) {
+        let now = Utc::now().timestamp();
+
+        if let Some(week) = data
+            .cache
+            .lock()
+            .await
+            .get_fresh_week(restaurant, monday, now)
+        {
+            return Ok(week.clone());
+        }
+
+        match restaurant.fetch_week().await {
+            Ok(week) if week.start == monday => {
+                data.cache
+                    .lock()
+                    .await
+                    .insert_week(restaurant, week.clone(), now);
+
+                return Ok(week);
+            }
+            Ok(_) => (),
+            Err(e) => {
+                println!("Error fetching weekly menu {restaurant}: {e:?}");
+
+                if let Some(week) = data.cache.lock().await.get_any_week(restaurant, monday) {
+                    return Ok(week.clone());
+                }
+            }
+        }
+    }
+
+    let mut days = Vec::new();
+    let mut fetched = false;
+    let mut error = None;
+
+    for date in monday.iter_days().take(7) {
+        // cached_day already logged why, the rest of the week is still worth showing
+        let menu = match cached_day(data, restaurant, &date.format("%Y-%m-%d").to_string()).await {
+            Ok(menu) => menu,
+            Err(e) => {
+                error = Some(e);
+                continue;
+            }
+        };
+        fetched = true;
+
+        if !menu.menu.dishes.is_empty() {
+            days.push(WeekDay {
+                date,
+                name: weekday_name(date.weekday(), Locale::Fi),
+                menu: menu.menu,
+            });
+        }
+    }
+
+    // an empty week would look like the restaurant is closed
+    if let Some(e) = error.filter(|_| !fetched) {
+        return Err(e);
+    }
+
+    Ok(Week {
+        start: monday,
+        end: monday + Days::new(6),
+        days,
+    })
+}
+
+#[test]
+fn serving_days() {
+    use crate::types::day::DailyMenu;
+
+    let daily = std::fs::read_to_string("tests/daily.json").expect("no file");
+    let menu: DailyMenu = serde_json::from_str(&daily).expect("unable to parse json");
+    let menu: Menu = menu.into();
+    let mut closed = menu.clone();
+    closed.dishes.clear();
+
+    let centria = RestaurantId::default();
+    let d = |day| NaiveDate::from_ymd_opt(2025, 10, day).expect("invalid date");
+
+    let mut cache = MenuCache::new(60);
+    // a friday with food, a monday without and a saturday with
+    cache.insert(&centria, "2025-10-17", menu.clone(), 1000);
+    cache.insert(&centria, "2025-10-18", menu, 1000);
+    cache.insert(&centria, "2025-10-20", closed, 1000);
+
+    let days = cache.serving_days(&centria, d(17), 4);
+    let dates = days.iter().map(|(d, _)| *d).collect::<Vec<NaiveDate>>();
+    assert_eq!(dates, vec![d(17), d(18), d(21), d(22)]);
+    assert!(days[0].1.is_some());
+    assert!(days[2].1.is_none());
+}
+
+#[test]
+fn cache_ttl() {
+    use crate::types::day::DailyMenu;
+
+    let daily = std::fs::read_to_string("tests/daily.json").expect("no file");
+    let menu: DailyMenu = serde_json::from_str(&daily).expect("unable to parse json");
+    let menu: Menu = menu.into();
+
+    let centria = RestaurantId::default();
+    let other: RestaurantId = "130".parse().expect("unable to parse id");
+
+    let mut cache = MenuCache::new(60);
+    cache.insert(&centria, "2025-08-27", menu.clone(), 1000);
+
+    assert!(cache.get_fresh(&centria, "2025-08-27", 1059).is_some());
+    assert!(cache.get_fresh(&centria, "2025-08-27", 1060).is_none());
+    assert!(cache.get_fresh(&other, "2025-08-27", 1000).is_none());
+    assert!(cache.get_fresh(&centria, "2025-08-28", 1000).is_none());
+
+    // an older generation of the same menu does not replace the cached one
+    let generated = menu.generated_timestamp;
+    let mut older = menu;
+    older.generated_timestamp -= 1;
+    cache.insert(&centria, "2025-08-27", older, 1100);
+
+    let cached = cache
+        .get_fresh(&centria, "2025-08-27", 1100)
+        .expect("not cached");
+    assert_eq!(cached.menu.generated_timestamp, generated);
+}
+
+#[test]
+fn outdated_note() {
+    use crate::types::day::DailyMenu;
+
+    let daily = std::fs::read_to_string("tests/daily.json").expect("no file");
+    let menu: DailyMenu = serde_json::from_str(&daily).expect("unable to parse json");
+    let menu: Menu = menu.into();
+
+    let fresh = FetchedMenu {
+        menu: menu.clone(),
+        outdated: None,
+    };
+    assert!(fresh.outdated_note(Locale::Fi).is_none());
+
+    // 2025-09-02 07:00 in Helsinki
+    let stale = FetchedMenu {
+        menu,
+        outdated: Some(1756785600),
+    };
+    assert_eq!(
+        stale.outdated_note(Locale::Fi).as_deref(),
+        Some("⚠️ Ruokalista voi olla vanhentunut, haettu 02.09. 07:00")
+    );
+    assert_eq!(
+        stale.outdated_note(Locale::En).as_deref(),
+        Some("⚠️ The menu may be out of date, fetched 02.09. 07:00")
+    );
+}
+
+#[test]
+fn week_cache() {
+    use crate::types::week::WeeklyMenu;
+
+    let weekly = std::fs::read_to_string("tests/weekly.json").expect("no file");
+    let menu: WeeklyMenu = serde_json::from_str(&weekly).expect("unable to parse json");
+    let today = NaiveDate::from_ymd_opt(2025, 8, 27).expect("invalid date");
+    let week = crate::provider::sodexo::week(menu, today).expect("unable to convert week");
+    let monday = week.start;
+    let centria = RestaurantId::default();
+
+    let mut cache = MenuCache::new(60);
+    cache.insert_week(&centria, week.clone(), 1000);
+
+    assert!(cache.get_fresh_week(&centria, monday, 1059).is_some());
+    assert!(cache.get_fresh_week(&centria, monday, 1060).is_none());
+    assert!(cache.get_any_week(&centria, monday).is_some());
+    assert!(
+        cache
+            .get_any_week(&centria, monday + Days::new(7))
+            .is_none()
+    );
+
+    // the days are cached for the info buttons
+    let first = week.days[0].date.format("%Y-%m-%d").to_string();
+    assert!(cache.get_fresh(&centria, &first, 1000).is_some());
+}
@@ -0,0 +1,241 @@
+use crate::Context;
+use crate::Error;
+use crate::follow::Follow;
+use crate::follow::FollowKind;
+use crate::follow::Follower;
+use crate::locale::Locale;
+use crate::settings::current_restaurant;
+use crate::settings::current_view;
+use poise::CreateReply;
+use serenity::all::AutocompleteChoice;
+
+fn fmt_follows(follower: &Follower, locale: Locale) -> String {
+    let t = locale.strings();
+    if follower.follows.is_empty() {
+        return t.no_follows.to_string();
+    }
+
+    let list = follower
+        .follows
+        .iter()
+        .enumerate()
+        .map(|(i, f)| format!("{}. {}\n", i + 1, f.describe(locale)))
+        .collect::<String>();
+
+    format!("{}\n{list}", t.follows_intro)
+}
+
+/// Changes the invoking user's follows, a user who follows nothing is removed
+async fn update_follows(
+    ctx: Context<'_>,
+    f: impl FnOnce(&mut Follower) -> Result<(), Error>,
+) -> Result<(), Error> {
+    let user_id = ctx.author().id.get();
+    let locale = current_view(ctx).await.locale;
+
+    let msg = {
+        let mut follows = ctx.data().follows.lock().await;
+        let follower = follows.users.entry(user_id).or_default();
+        follower.locale = locale;
+        let result = f(follower);
+
+        let msg = fmt_follows(follower, locale);
+        if follower.follows.is_empty() {
+            follows.users.remove(&user_id);
+        }
+        result?;
+        follows.save()?;
+
+        msg
+    };
+
+    ctx.send(CreateReply::default().content(msg).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+async fn follow_query(ctx: Context<'_>, kind: FollowKind, query: &str) -> Result<(), Error> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Err(current_view(ctx)
+            .await
+            .locale
+            .strings()
+            .missing_query
+            .into());
+    }
+
+    let follow = Follow {
+        kind,
+        query,
+        restaurant: current_restaurant(ctx).await,
+    };
+
+    update_follows(ctx, |f| {
+        if !f.follows.contains(&follow) {
+            f.follows.push(follow);
+        }
+
+        Ok(())
+    })
+    .await
+}
+
+async fn autocomplete_follow(ctx: Context<'_>, partial: &str) -> Vec<AutocompleteChoice> {
+    let partial = partial.trim().to_lowercase();
+    let locale = current_view(ctx).await.locale;
+
+    ctx.data()
+        .follows
+        .lock()
+        .await
+        .users
+        .get(&ctx.author().id.get())
+        .map(|f| f.follows.clone())
+        .unwrap_or_default()
+        .iter()
+        .enumerate()
+        .filter(|(_, f)| f.query.contains(&partial))
+        .map(|(i, f)| AutocompleteChoice::new(f.describe(locale), (i + 1).to_string()))
+        .collect()
+}
+
+/// Seuratut ruoat, joista saat yksityisviestin kun ne ovat tulossa ruokalistalle
+#[poise::command(
+    slash_command,
+    rename = "seuraa",
+    subcommands("follow_dish", "follow_ingredient", "show_follows", "unfollow"),
+    subcommand_required,
+    name_localized("en-US", "follow"),
+    description_localized(
+        "en-US",
+        "Followed dishes, you get a DM when they're coming up on the menu"
+    ),
+    name_localized("en-GB", "follow"),
+    description_localized(
+        "en-GB",
+        "Followed dishes, you get a DM when they're coming up on the menu"
+    )
+)]
+pub async fn follow(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Seuraa ruokaa nimen perusteella kanavan ravintolassa, pienet kirjoitusvirheet sallitaan
+#[poise::command(
+    slash_command,
+    rename = "ruoka",
+    name_localized("en-US", "dish"),
+    description_localized(
+        "en-US",
+        "Follows a dish by name in the channel's restaurant, small typos are allowed"
+    ),
+    name_localized("en-GB", "dish"),
+    description_localized(
+        "en-GB",
+        "Follows a dish by name in the channel's restaurant, small typos are allowed"
+    )
+)]
+pub async fn follow_dish(
+    ctx: Context<'_>,
+    #[description = "Ruoan nimi, esim. `lihakeitto`"]
+    #[name_localized("en-US", "name")]
+    #[name_localized("en-GB", "name")]
+    #[description_localized("en-US", "Name of the dish, like `meat soup`")]
+    #[description_localized("en-GB", "Name of the dish, like `meat soup`")]
+    nimi: String,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    follow_query(ctx, FollowKind::Dish, &nimi).await
+}
+
+/// Seuraa ruokia, joissa on ainesosa, esim. `lohi`
+#[poise::command(
+    slash_command,
+    rename = "ainesosa",
+    name_localized("en-US", "ingredient"),
+    description_localized("en-US", "Follows dishes with an ingredient, in finnish like `lohi`"),
+    name_localized("en-GB", "ingredient"),
+    description_localized("en-GB", "Follows dishes with an ingredient, in finnish like `lohi`")
+)]
+pub async fn follow_ingredient(
+    ctx: Context<'_>,
+    #[description = "Ainesosa, joka etsitään ruoan nimestä ja ainesosista"]
+    #[name_localized("en-US", "ingredient")]
+    #[name_localized("en-GB", "ingredient")]
+    #[description_localized("en-US", "Ingredient looked for in the dish's name and ingredients")]
+    #[description_localized("en-GB", "Ingredient looked for in the dish's name and ingredients")]
+    ainesosa: String,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    follow_query(ctx, FollowKind::Ingredient, &ainesosa).await
+}
+
+/// Näyttää seuraamasi ruoat ja ainesosat
+#[poise::command(
+    slash_command,
+    rename = "nayta",
+    name_localized("en-US", "show"),
+    description_localized("en-US", "Shows the dishes and ingredients you follow"),
+    name_localized("en-GB", "show"),
+    description_localized("en-GB", "Shows the dishes and ingredients you follow")
+)]
+pub async fn show_follows(ctx: Context<'_>) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let locale = current_view(ctx).await.locale;
+    let follower = ctx
+        .data()
+        .follows
+        .lock()
+        .await
+        .users
+        .get(&ctx.author().id.get())
+        .cloned()
+        .unwrap_or_default();
+
+    ctx.send(
+        CreateReply::default()
+            .content(fmt_follows(&follower, locale))
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Lopettaa seuraamisen
+#[poise::command(
+    slash_command,
+    rename = "lopeta",
+    name_localized("en-US", "stop"),
+    description_localized("en-US", "Stops following"),
+    name_localized("en-GB", "stop"),
+    description_localized("en-GB", "Stops following")
+)]
+pub async fn unfollow(
+    ctx: Context<'_>,
+    #[description = "Seuranta tai sen numero komennosta `/seuraa nayta`"]
+    #[autocomplete = "autocomplete_follow"]
+    #[name_localized("en-US", "follow")]
+    #[name_localized("en-GB", "follow")]
+    #[description_localized("en-US", "The follow or its number from `/follow show`")]
+    #[description_localized("en-GB", "The follow or its number from `/follow show`")]
+    seuranta: String,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let t = current_view(ctx).await.locale.strings();
+
+    update_follows(ctx, |f| match f.find(&seuranta) {
+        Some(i) => {
+            f.follows.remove(i);
+            Ok(())
+        }
+        None => Err(format!("{} `{seuranta}`", t.unknown_follow).into()),
+    })
+    .await
+}
@@ -0,0 +1,165 @@
+use crate::Context;
+use crate::Error;
+use crate::cache::cached_day;
+use crate::commands::list::autocomplete_day;
+use crate::dates::parse_date;
+use crate::list::group::fmt_group;
+use crate::profile::group_fit;
+use crate::settings::current_course_order;
+use crate::settings::current_restaurant;
+use crate::settings::current_view;
+use poise::CreateReply;
+use poise::serenity_prelude as serenity;
+
+/// Näyttää ruoat, jotka sopivat kaikkien ryhmäläisten profiileihin
+///
+/// Ryhmään kuuluu aina komennon käyttäjä, annetut käyttäjät ja roolin jäsenet.
+/// Vain ryhmille jaetut profiilit huomioidaan, koska syyt näkyvät koko ryhmälle.
+#[allow(clippy::too_many_arguments)] // poise takes every option as an argument
+#[poise::command(
+    slash_command,
+    rename = "ryhma",
+    guild_only,
+    name_localized("en-US", "group"),
+    description_localized(
+        "en-US",
+        "Shows the dishes that suit the profiles of everyone in the group"
+    ),
+    name_localized("en-GB", "group"),
+    description_localized(
+        "en-GB",
+        "Shows the dishes that suit the profiles of everyone in the group"
+    )
+)]
+pub async fn group_menu(
+    ctx: Context<'_>,
+    #[description = "Käyttäjä"]
+    #[name_localized("en-US", "user")]
+    #[name_localized("en-GB", "user")]
+    #[description_localized("en-US", "User")]
+    #[description_localized("en-GB", "User")]
+    kayttaja: Option<serenity::User>,
+    #[description = "Käyttäjä"]
+    #[name_localized("en-US", "user2")]
+    #[name_localized("en-GB", "user2")]
+    #[description_localized("en-US", "User")]
+    #[description_localized("en-GB", "User")]
+    kayttaja2: Option<serenity::User>,
+    #[description = "Käyttäjä"]
+    #[name_localized("en-US", "user3")]
+    #[name_localized("en-GB", "user3")]
+    #[description_localized("en-US", "User")]
+    #[description_localized("en-GB", "User")]
+    kayttaja3: Option<serenity::User>,
+    #[description = "Käyttäjä"]
+    #[name_localized("en-US", "user4")]
+    #[name_localized("en-GB", "user4")]
+    #[description_localized("en-US", "User")]
+    #[description_localized("en-GB", "User")]
+    kayttaja4: Option<serenity::User>,
+    #[description = "Roolin jäsenet, jotka ovat jakaneet profiilinsa tällä palvelimella"]
+    #[name_localized("en-US", "role")]
+    #[name_localized("en-GB", "role")]
+    #[description_localized(
+        "en-US",
+        "Members of the role who have shared their profile on this server"
+    )]
+    #[description_localized(
+        "en-GB",
+        "Members of the role who have shared their profile on this server"
+    )]
+    rooli: Option<serenity::Role>,
+    #[description = "Päivämäärä (24.12. tai YYYY-MM-DD), viikonpäivä, huomenna tai offset (+n) päivää"]
+    #[autocomplete = "autocomplete_day"]
+    #[name_localized("en-US", "day")]
+    #[name_localized("en-GB", "day")]
+    #[description_localized(
+        "en-US",
+        "Date (24.12. or YYYY-MM-DD), weekday, tomorrow or offset (+n) in days"
+    )]
+    #[description_localized(
+        "en-GB",
+        "Date (24.12. or YYYY-MM-DD), weekday, tomorrow or offset (+n) in days"
+    )]
+    paiva: Option<String>,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let guild_id = ctx.guild_id().ok_or("not in a guild")?;
+
+    let today = chrono::Local::now().date_naive();
+    let day = match paiva {
+        Some(day) => parse_date(&day, today)?,
+        None => today,
+    }
+    .format("%Y-%m-%d")
+    .to_string();
+
+    let mut members: Vec<u64> = Vec::new();
+    for user in [
+        Some(ctx.author()),
+        kayttaja.as_ref(),
+        kayttaja2.as_ref(),
+        kayttaja3.as_ref(),
+        kayttaja4.as_ref(),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        if !members.contains(&user.id.get()) {
+            members.push(user.id.get());
+        }
+    }
+
+    // members without a shared profile aren't checked, so only the ones who
+    // have shared theirs on this guild are looked up
+    let guild_users = ctx.data().profiles.lock().await.guild_users(guild_id.get());
+
+    if let Some(role) = &rooli {
+        for id in guild_users {
+            if members.contains(&id) {
+                continue;
+            }
+
+            match guild_id.member(ctx, id).await {
+                Ok(member) if member.roles.contains(&role.id) => members.push(id),
+                Ok(_) => (),
+                Err(e) => println!("unable to get member {id}: {e:?}"),
+            }
+        }
+    }
+
+    let mut group = Vec::new();
+    let mut unshared = Vec::new();
+    {
+        let profiles = ctx.data().profiles.lock().await;
+        for id in &members {
+            match profiles.shared(*id) {
+                Some(profile) => group.push((*id, profile.clone())),
+                None => unshared.push(*id),
+            }
+        }
+    }
+
+    let restaurant = current_restaurant(ctx).await;
+    let mut menu = cached_day(ctx.data(), &restaurant, &day).await?;
+    menu.menu.sort_dishes(current_course_order(ctx).await);
+    let locale = current_view(ctx).await.locale;
+
+    let msg = match menu.menu.dishes.is_empty() {
+        true => format!("{} {day}", locale.strings().no_menu_for_day),
+        false => fmt_group(
+            &day,
+            &menu.menu,
+            &members,
+            &unshared,
+            &group_fit(&menu.menu.dishes, &group),
+            locale,
+        ),
+    };
+
+    ctx.send(CreateReply::default().content(msg).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
@@ -1,44 +1,130 @@
-use chrono::Datelike;
-use crate::types::day::DailyMenu;
-use crate::list::fetch_week;
+use crate::cache::cached_day;
+use crate::cache::cached_week;
+use crate::dates::parse_date;
+use crate::dates::parse_week;
+use crate::dates::week_start;
+use crate::dates::weekday_short;
 use crate::list::fmt_day;
-use crate::list::fetch_day;
-use chrono::Days;
+use crate::list::truncate;
+use crate::list::week::fmt_week;
+use crate::menu::DietFilter;
+use crate::menu::DietRestriction;
+use crate::settings::View;
+use crate::settings::current_course_order;
+use crate::settings::current_restaurant;
+use crate::settings::current_view;
 use crate::{Context, Error};
+use chrono::Datelike;
+use poise::CreateReply;
+use serenity::all::AutocompleteChoice;
+
+/// Discord allows at most this many characters in a choice's name
+const CHOICE_NAME_MAX: usize = 100;
+
+/// Suggests the next week's days with a preview of their menus, only cached
+/// menus are shown since discord waits for suggestions just 3 seconds
+pub async fn autocomplete_day(ctx: Context<'_>, partial: &str) -> Vec<AutocompleteChoice> {
+    let today = chrono::Local::now().date_naive();
+    let restaurant = current_restaurant(ctx).await;
+    let locale = current_view(ctx).await.locale;
+    let partial = partial.trim().to_lowercase();
+
+    let cache = ctx.data().cache.lock().await;
+
+    cache
+        .serving_days(&restaurant, today, 7)
+        .into_iter()
+        .map(|(date, menu)| {
+            let mut name = format!(
+                "{} {}",
+                weekday_short(date.weekday(), locale),
+                date.format("%d.%m.")
+            );
 
+            if let Some(menu) = menu {
+                let dishes = menu
+                    .dishes
+                    .iter()
+                    .map(|d| d.title(locale))
+                    .collect::<Vec<&str>>()
+                    .join(", ");
+                name = truncate(&format!("{name} — {dishes}"), CHOICE_NAME_MAX);
+            }
+
+            (name, date.format("%Y-%m-%d").to_string())
+        })
+        .filter(|(name, _)| partial.is_empty() || name.to_lowercase().contains(&partial))
+        .map(|(name, date)| AutocompleteChoice::new(name, date))
+        .collect()
+}
+
+/// Näyttää päivän ruokalistan
 #[poise::command(
     slash_command,
     rename = "ruokalista",
+    name_localized("en-US", "menu"),
+    description_localized("en-US", "Shows the menu of a day"),
+    name_localized("en-GB", "menu"),
+    description_localized("en-GB", "Shows the menu of a day")
 )]
 pub async fn daily_menu(
     ctx: Context<'_>,
-    #[description = "Päivämäärä (YYYY-MM-DD) tai offset (+n) päivää"] day: Option<String>,
+    #[description = "Päivämäärä (24.12. tai YYYY-MM-DD), viikonpäivä, huomenna tai offset (+n) päivää"]
+    #[autocomplete = "autocomplete_day"]
+    #[name_localized("en-US", "day")]
+    #[name_localized("en-GB", "day")]
+    #[description_localized(
+        "en-US",
+        "Date (24.12. or YYYY-MM-DD), weekday, tomorrow or offset (+n) in days"
+    )]
+    #[description_localized(
+        "en-GB",
+        "Date (24.12. or YYYY-MM-DD), weekday, tomorrow or offset (+n) in days"
+    )]
+    day: Option<String>,
+    #[description = "Ruokavalion rajoitus"]
+    #[name_localized("en-US", "diet")]
+    #[name_localized("en-GB", "diet")]
+    #[description_localized("en-US", "Diet restriction")]
+    #[description_localized("en-GB", "Diet restriction")]
+    rajoitus: Option<DietRestriction>,
+    #[description = "Ruokavalion rajoitus"]
+    #[name_localized("en-US", "diet2")]
+    #[name_localized("en-GB", "diet2")]
+    #[description_localized("en-US", "Diet restriction")]
+    #[description_localized("en-GB", "Diet restriction")]
+    rajoitus2: Option<DietRestriction>,
+    #[description = "Ruokavalion rajoitus"]
+    #[name_localized("en-US", "diet3")]
+    #[name_localized("en-GB", "diet3")]
+    #[description_localized("en-US", "Diet restriction")]
+    #[description_localized("en-GB", "Diet restriction")]
+    rajoitus3: Option<DietRestriction>,
 ) -> Result<(), Error> {
     ctx.defer_ephemeral().await?;
 
+    let today = chrono::Local::now().date_naive();
     let day = match day {
-        Some(day) => {
-            if &day[0..1] == "+" {
-                let n = &day[1..].parse::<u64>()?;
-                let date = chrono::Local::now();
-
-                date.checked_add_days(Days::new(*n))
-                    .ok_or("invalid number of days")?
-                    .format("%Y-%m-%d")
-                    .to_string()
-            } else {
-                day
-            }
-        }
-        None => chrono::Local::now()
-            .date_naive()
-            .format("%Y-%m-%d")
-            .to_string(),
-    };
+        Some(day) => parse_date(&day, today)?,
+        None => today,
+    }
+    .format("%Y-%m-%d")
+    .to_string();
 
-    let menu = fetch_day(&day).await?;
+    let restaurant = current_restaurant(ctx).await;
+    let mut menu = cached_day(ctx.data(), &restaurant, &day).await?;
+    menu.menu.sort_dishes(current_course_order(ctx).await);
+    let filter = [rajoitus, rajoitus2, rajoitus3]
+        .into_iter()
+        .flatten()
+        .collect::<DietFilter>();
+    let view = View {
+        filter,
+        ..current_view(ctx).await
+    };
+    let note = menu.outdated_note(view.locale);
 
-    let reply = fmt_day(&day, menu, None);
+    let reply = fmt_day(&restaurant, &day, menu.menu, note.as_deref(), &view);
 
     // send the message
     ctx.send(reply.ephemeral(true)).await?;
@@ -46,34 +132,73 @@ pub async fn daily_menu(
     Ok(())
 }
 
+/// Näyttää viikon ruokalistan päivä kerrallaan
 #[poise::command(
     slash_command,
-    rename = "viikon-ruokalista"
+    rename = "viikon-ruokalista",
+    name_localized("en-US", "weekly-menu"),
+    description_localized("en-US", "Shows the menu of a week one day at a time"),
+    name_localized("en-GB", "weekly-menu"),
+    description_localized("en-GB", "Shows the menu of a week one day at a time")
 )]
-pub async fn weekly_menu(ctx: Context<'_>) -> Result<(), Error> {
+pub async fn weekly_menu(
+    ctx: Context<'_>,
+    #[description = "Viikon numero, offset (+n) viikkoa tai päivämäärä (YYYY-MM-DD)"]
+    #[name_localized("en-US", "week")]
+    #[name_localized("en-GB", "week")]
+    #[description_localized("en-US", "Week number, offset (+n) in weeks or a date (YYYY-MM-DD)")]
+    #[description_localized("en-GB", "Week number, offset (+n) in weeks or a date (YYYY-MM-DD)")]
+    viikko: Option<String>,
+    #[description = "Ruokavalion rajoitus"]
+    #[name_localized("en-US", "diet")]
+    #[name_localized("en-GB", "diet")]
+    #[description_localized("en-US", "Diet restriction")]
+    #[description_localized("en-GB", "Diet restriction")]
+    rajoitus: Option<DietRestriction>,
+    #[description = "Ruokavalion rajoitus"]
+    #[name_localized("en-US", "diet2")]
+    #[name_localized("en-GB", "diet2")]
+    #[description_localized("en-US", "Diet restriction")]
+    #[description_localized("en-GB", "Diet restriction")]
+    rajoitus2: Option<DietRestriction>,
+    #[description = "Ruokavalion rajoitus"]
+    #[name_localized("en-US", "diet3")]
+    #[name_localized("en-GB", "diet3")]
+    #[description_localized("en-US", "Diet restriction")]
+    #[description_localized("en-GB", "Diet restriction")]
+    rajoitus3: Option<DietRestriction>,
+) -> Result<(), Error> {
     ctx.defer_ephemeral().await?;
 
-    let menu = fetch_week().await?;
-    let date: Vec<&str> = menu.timeperiod.split('.').collect();
-    let day = date.first().ok_or("invalid date in json")?;
-
-    // TODO this is garbage
-    let date = chrono::Local::now()
-        .with_day(day.parse()?)
-        .ok_or("invalid day")?;
+    let today = chrono::Local::now().date_naive();
+    let monday = match viikko {
+        Some(week) => parse_week(&week, today)?,
+        None => week_start(today),
+    };
 
-    let menus: Vec<(String, DailyMenu)> = menu.into();
+    let restaurant = current_restaurant(ctx).await;
+    let order = current_course_order(ctx).await;
+    let filter = [rajoitus, rajoitus2, rajoitus3]
+        .into_iter()
+        .flatten()
+        .collect::<DietFilter>();
+    let view = View {
+        filter,
+        ..current_view(ctx).await
+    };
+    let week = cached_week(ctx.data(), &restaurant, monday).await?;
 
-    for (i, (n, m)) in menus.into_iter().enumerate() {
-        let day = date
-            .checked_add_days(Days::new(i as u64))
-            .ok_or("day somehow invalid")?
-            .format("%Y-%m-%d")
-            .to_string();
+    let reply = match week.days.is_empty() {
+        true => CreateReply::default().content(format!(
+            "{} {} - {}",
+            view.locale.strings().no_menu_for_week,
+            week.start.format("%d.%m."),
+            week.end.format("%d.%m.%Y")
+        )),
+        false => fmt_week(&restaurant, week, today, order, &view),
+    };
 
-        let reply = fmt_day(&day, m, Some(&n));
-        ctx.send(reply).await?;
-    }
+    ctx.send(reply.ephemeral(true)).await?;
 
     Ok(())
 }
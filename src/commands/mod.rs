@@ -1,5 +1,10 @@
 pub mod schedule;
+pub mod follow;
+pub mod group;
 pub mod list;
+pub mod profile;
+pub mod restaurants;
+pub mod settings;
 
 pub mod builtin {
     use crate::{Context, Error};
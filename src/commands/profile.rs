@@ -0,0 +1,334 @@
+use crate::Context;
+use crate::Error;
+use crate::locale::Locale;
+use crate::menu::DietRestriction;
+use crate::menu::allergen::AllergenKind;
+use crate::menu::allergen::KINDS;
+use crate::profile::Profile;
+use crate::settings::current_view;
+use poise::CreateReply;
+use serenity::all::AutocompleteChoice;
+
+fn fmt_profile(profile: &Profile, locale: Locale) -> String {
+    let t = locale.strings();
+    if profile.is_empty() {
+        return t.profile_empty.to_string();
+    }
+
+    let list = |items: Vec<&str>| match items.is_empty() {
+        true => "-".to_string(),
+        false => items.join(", "),
+    };
+
+    format!(
+        r"{}: {}
+{}: {}
+{}: {}
+{}: {}
+{}: {}",
+        t.diet,
+        list(profile.diet.labels(locale)),
+        t.profile_allergens,
+        list(
+            profile
+                .allergens
+                .iter()
+                .filter_map(|a| a.name(locale))
+                .collect()
+        ),
+        t.profile_ingredients,
+        list(profile.ingredients.iter().map(String::as_str).collect()),
+        t.conflicting_dishes,
+        match profile.hide_conflicts {
+            true => t.conflicts_hidden,
+            false => t.conflicts_marked,
+        },
+        t.group_checks,
+        match profile.share_in_groups {
+            true => t.shared_in_groups,
+            false => t.not_shared_in_groups,
+        }
+    )
+}
+
+/// Changes the invoking user's profile, an emptied profile is removed
+async fn update_profile(ctx: Context<'_>, f: impl FnOnce(&mut Profile)) -> Result<(), Error> {
+    let user_id = ctx.author().id.get();
+    let locale = current_view(ctx).await.locale;
+
+    let msg = {
+        let mut profiles = ctx.data().profiles.lock().await;
+        let profile = profiles.users.entry(user_id).or_default();
+        f(profile);
+
+        let msg = fmt_profile(profile, locale);
+        if *profile == Profile::default() {
+            profiles.remove(user_id);
+        } else if let Some(guild_id) = ctx.guild_id() {
+            profiles.join_guild(guild_id.get(), user_id);
+        }
+        profiles.save()?;
+
+        msg
+    };
+
+    ctx.send(CreateReply::default().content(msg).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+async fn autocomplete_allergen(_ctx: Context<'_>, partial: &str) -> Vec<AutocompleteChoice> {
+    let partial = partial.trim().to_lowercase();
+
+    KINDS
+        .iter()
+        .filter(|(_, fi, en)| {
+            fi.to_lowercase().contains(&partial) || en.to_lowercase().contains(&partial)
+        })
+        .map(|(_, fi, _)| AutocompleteChoice::new(*fi, *fi))
+        .collect()
+}
+
+/// Ruokavaliosi, jonka perusteella sopimattomat ruoat merkitään tai piilotetaan
+#[poise::command(
+    slash_command,
+    rename = "profiili",
+    subcommands(
+        "show_profile",
+        "set_diet",
+        "avoid_allergen",
+        "avoid_ingredient",
+        "hide_conflicts",
+        "share_profile",
+        "clear_profile"
+    ),
+    subcommand_required,
+    name_localized("en-US", "profile"),
+    description_localized("en-US", "Your diet, dishes that don't suit it are marked or hidden"),
+    name_localized("en-GB", "profile"),
+    description_localized("en-GB", "Your diet, dishes that don't suit it are marked or hidden")
+)]
+pub async fn profile(_ctx: Context<'_>) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Näyttää profiilisi
+#[poise::command(
+    slash_command,
+    rename = "nayta",
+    name_localized("en-US", "show"),
+    description_localized("en-US", "Shows your profile"),
+    name_localized("en-GB", "show"),
+    description_localized("en-GB", "Shows your profile")
+)]
+pub async fn show_profile(ctx: Context<'_>) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let view = current_view(ctx).await;
+
+    ctx.send(
+        CreateReply::default()
+            .content(fmt_profile(&view.profile, view.locale))
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Lisää tai poistaa ruokavaliosi rajoituksen
+#[poise::command(
+    slash_command,
+    rename = "ruokavalio",
+    name_localized("en-US", "diet"),
+    description_localized("en-US", "Adds or removes a restriction of your diet"),
+    name_localized("en-GB", "diet"),
+    description_localized("en-GB", "Adds or removes a restriction of your diet")
+)]
+pub async fn set_diet(
+    ctx: Context<'_>,
+    #[description = "Ruokavalion rajoitus"]
+    #[name_localized("en-US", "restriction")]
+    #[name_localized("en-GB", "restriction")]
+    #[description_localized("en-US", "Diet restriction")]
+    #[description_localized("en-GB", "Diet restriction")]
+    rajoitus: DietRestriction,
+    #[description = "Poista rajoitus profiilista"]
+    #[name_localized("en-US", "remove")]
+    #[name_localized("en-GB", "remove")]
+    #[description_localized("en-US", "Remove the restriction from your profile")]
+    #[description_localized("en-GB", "Remove the restriction from your profile")]
+    poista: Option<bool>,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    update_profile(ctx, |p| p.diet.set(rajoitus, !poista.unwrap_or(false))).await
+}
+
+/// Lisää tai poistaa allergeenin, jota vältät
+#[poise::command(
+    slash_command,
+    rename = "allergeeni",
+    name_localized("en-US", "allergen"),
+    description_localized("en-US", "Adds or removes an allergen you avoid"),
+    name_localized("en-GB", "allergen"),
+    description_localized("en-GB", "Adds or removes an allergen you avoid")
+)]
+pub async fn avoid_allergen(
+    ctx: Context<'_>,
+    #[description = "Allergeeni"]
+    #[autocomplete = "autocomplete_allergen"]
+    #[name_localized("en-US", "allergen")]
+    #[name_localized("en-GB", "allergen")]
+    #[description_localized("en-US", "Allergen")]
+    #[description_localized("en-GB", "Allergen")]
+    allergeeni: String,
+    #[description = "Poista allergeeni profiilista"]
+    #[name_localized("en-US", "remove")]
+    #[name_localized("en-GB", "remove")]
+    #[description_localized("en-US", "Remove the allergen from your profile")]
+    #[description_localized("en-GB", "Remove the allergen from your profile")]
+    poista: Option<bool>,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let t = current_view(ctx).await.locale.strings();
+    let kind = AllergenKind::from_name(&allergeeni).ok_or(format!(
+        "{} `{allergeeni}`, {}",
+        t.unknown_allergen, t.avoid_as_ingredient
+    ))?;
+
+    update_profile(ctx, |p| {
+        p.allergens.retain(|a| *a != kind);
+        if !poista.unwrap_or(false) {
+            p.allergens.push(kind);
+            p.allergens.sort();
+        }
+    })
+    .await
+}
+
+/// Lisää tai poistaa ainesosan, jota vältät, esim. `sieni` tai `herneet`
+#[poise::command(
+    slash_command,
+    rename = "ainesosa",
+    name_localized("en-US", "ingredient"),
+    description_localized(
+        "en-US",
+        "Adds or removes an ingredient you avoid, in finnish like `sieni`"
+    ),
+    name_localized("en-GB", "ingredient"),
+    description_localized(
+        "en-GB",
+        "Adds or removes an ingredient you avoid, in finnish like `sieni`"
+    )
+)]
+pub async fn avoid_ingredient(
+    ctx: Context<'_>,
+    #[description = "Ainesosa, joka etsitään ruoan nimestä ja ainesosista"]
+    #[name_localized("en-US", "ingredient")]
+    #[name_localized("en-GB", "ingredient")]
+    #[description_localized("en-US", "Ingredient looked for in the dish's name and ingredients")]
+    #[description_localized("en-GB", "Ingredient looked for in the dish's name and ingredients")]
+    ainesosa: String,
+    #[description = "Poista ainesosa profiilista"]
+    #[name_localized("en-US", "remove")]
+    #[name_localized("en-GB", "remove")]
+    #[description_localized("en-US", "Remove the ingredient from your profile")]
+    #[description_localized("en-GB", "Remove the ingredient from your profile")]
+    poista: Option<bool>,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let ingredient = ainesosa.trim().to_lowercase();
+    if ingredient.is_empty() {
+        return Err(current_view(ctx)
+            .await
+            .locale
+            .strings()
+            .missing_ingredient
+            .into());
+    }
+
+    update_profile(ctx, |p| {
+        p.ingredients.retain(|i| *i != ingredient);
+        if !poista.unwrap_or(false) {
+            p.ingredients.push(ingredient);
+        }
+    })
+    .await
+}
+
+/// Valitsee, piilotetaanko profiiliisi sopimattomat ruoat vai merkitäänkö ne
+#[poise::command(
+    slash_command,
+    rename = "piilota",
+    name_localized("en-US", "hide"),
+    description_localized(
+        "en-US",
+        "Chooses whether dishes that don't suit you are hidden or marked"
+    ),
+    name_localized("en-GB", "hide"),
+    description_localized(
+        "en-GB",
+        "Chooses whether dishes that don't suit you are hidden or marked"
+    )
+)]
+pub async fn hide_conflicts(
+    ctx: Context<'_>,
+    #[description = "Piilota sopimattomat ruoat"]
+    #[name_localized("en-US", "hide")]
+    #[name_localized("en-GB", "hide")]
+    #[description_localized("en-US", "Hide the dishes that don't suit you")]
+    #[description_localized("en-GB", "Hide the dishes that don't suit you")]
+    piilota: bool,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    update_profile(ctx, |p| p.hide_conflicts = piilota).await
+}
+
+/// Valitsee, käyttääkö `/ryhma` profiiliasi, ryhmä näkee miksi ruoka ei sovi sinulle
+#[poise::command(
+    slash_command,
+    rename = "jaa",
+    name_localized("en-US", "share"),
+    description_localized(
+        "en-US",
+        "Chooses whether `/group` uses your profile, the group sees why a dish doesn't suit you"
+    ),
+    name_localized("en-GB", "share"),
+    description_localized(
+        "en-GB",
+        "Chooses whether `/group` uses your profile, the group sees why a dish doesn't suit you"
+    )
+)]
+pub async fn share_profile(
+    ctx: Context<'_>,
+    #[description = "Jaa profiili ryhmille"]
+    #[name_localized("en-US", "share")]
+    #[name_localized("en-GB", "share")]
+    #[description_localized("en-US", "Share your profile with groups")]
+    #[description_localized("en-GB", "Share your profile with groups")]
+    jaa: bool,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    update_profile(ctx, |p| p.share_in_groups = jaa).await
+}
+
+/// Tyhjentää profiilisi
+#[poise::command(
+    slash_command,
+    rename = "tyhjenna",
+    name_localized("en-US", "clear"),
+    description_localized("en-US", "Clears your profile"),
+    name_localized("en-GB", "clear"),
+    description_localized("en-GB", "Clears your profile")
+)]
+pub async fn clear_profile(ctx: Context<'_>) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    update_profile(ctx, |p| *p = Profile::default()).await
+}
@@ -0,0 +1,93 @@
+use crate::Context;
+use crate::Error;
+use crate::restaurants::refresh;
+use crate::settings::current_view;
+use poise::CreateReply;
+
+/// Hakee ravintoloita nimen, kaupungin tai numeron perusteella
+#[poise::command(
+    slash_command,
+    rename = "ravintolat",
+    name_localized("en-US", "restaurants"),
+    description_localized("en-US", "Searches restaurants by name, city or number"),
+    name_localized("en-GB", "restaurants"),
+    description_localized("en-GB", "Searches restaurants by name, city or number")
+)]
+pub async fn search_restaurants(
+    ctx: Context<'_>,
+    #[description = "Hakusana"]
+    #[name_localized("en-US", "search")]
+    #[name_localized("en-GB", "search")]
+    #[description_localized("en-US", "Search term")]
+    #[description_localized("en-GB", "Search term")]
+    haku: Option<String>,
+    #[description = "Päivitä ravintoloiden tiedot Sodexolta, vaatii viestien hallintaoikeuden"]
+    #[name_localized("en-US", "refresh")]
+    #[name_localized("en-GB", "refresh")]
+    #[description_localized(
+        "en-US",
+        "Refresh the restaurants' details from Sodexo, requires the manage messages permission"
+    )]
+    #[description_localized(
+        "en-GB",
+        "Refresh the restaurants' details from Sodexo, requires the manage messages permission"
+    )]
+    paivita: Option<bool>,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let t = current_view(ctx).await.locale.strings();
+    let mut msg = String::new();
+
+    if paivita.unwrap_or(false) {
+        // refreshing fetches every restaurant and rewrites the directory
+        let can_manage = ctx
+            .author_member()
+            .await
+            .and_then(|m| m.permissions)
+            .is_some_and(|p| p.manage_messages());
+        if !can_manage {
+            return Err(t.refresh_needs_permission.into());
+        }
+
+        let refreshed = refresh(ctx).await?;
+
+        for (label, ids) in [
+            (t.restaurants_discovered, &refreshed.discovered),
+            (t.refresh_failed, &refreshed.failed),
+        ] {
+            if !ids.is_empty() {
+                msg += &format!(
+                    "{label} {}\n\n",
+                    ids.iter()
+                        .map(|id| format!("`{id}`"))
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                );
+            }
+        }
+    }
+
+    let results = {
+        let directory = ctx.data().restaurants.lock().await;
+
+        directory
+            .search(haku.as_deref().unwrap_or_default())
+            .into_iter()
+            // keep the message under discord's length limit
+            .take(20)
+            .map(|r| format!("- `{}` [{}, {}](<{}>)", r.id, r.name, r.city, r.ref_url))
+            .collect::<Vec<String>>()
+    };
+
+    if results.is_empty() {
+        msg += t.no_restaurants;
+    } else {
+        msg += &results.join("\n");
+    }
+
+    ctx.send(CreateReply::default().content(msg).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
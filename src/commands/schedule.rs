@@ -1,6 +1,7 @@
 use crate::Context;
 use crate::Error;
 use crate::schedule::{DataJob, create_scheduled_day_post, save_jobs};
+use crate::settings::current_view;
 use poise::CreateReply;
 use uuid::Uuid;
 
@@ -14,19 +15,39 @@ use uuid::Uuid;
 #[poise::command(
     slash_command,
     required_permissions = "SEND_MESSAGES | MANAGE_MESSAGES",
-    required_bot_permissions = "SEND_MESSAGES | MANAGE_MESSAGES"
+    required_bot_permissions = "SEND_MESSAGES | MANAGE_MESSAGES",
+    description_localized(
+        "en-US",
+        "Schedules a daily menu post, the schedule is a cron expression with seconds"
+    ),
+    description_localized(
+        "en-GB",
+        "Schedules a daily menu post, the schedule is a cron expression with seconds"
+    )
 )]
 pub async fn schedule_day(
     ctx: Context<'_>,
-    #[description = "Cron ajastus"] cron: String,
+    #[description = "Cron ajastus"]
+    #[description_localized("en-US", "Cron schedule")]
+    #[description_localized("en-GB", "Cron schedule")]
+    cron: String,
 ) -> Result<(), Error> {
     ctx.defer_ephemeral().await?;
 
     let channel_id = ctx.channel_id();
 
-    let msg = format!("Ajoitettu ruokalista luotu ajastuksella {cron}");
+    let t = current_view(ctx).await.locale.strings();
+    let msg = format!("{} {cron}", t.schedule_created);
+
+    let guild_id = ctx.guild_id().map(|g| g.get());
 
-    let job = create_scheduled_day_post(ctx.serenity_context(), &cron, channel_id)?;
+    let job = create_scheduled_day_post(
+        ctx.serenity_context(),
+        ctx.data().clone(),
+        &cron,
+        channel_id,
+        guild_id,
+    )?;
 
     {
         let mut jobs = ctx.data().job_uuids.lock().await;
@@ -35,6 +56,7 @@ pub async fn schedule_day(
             uuid: job.guid(),
             cron: cron.into(),
             channel_id: channel_id.get(),
+            guild_id,
         });
     }
 
@@ -54,15 +76,19 @@ pub async fn schedule_day(
     Ok(())
 }
 
+/// Listaa kanavan ajastetut ruokalistat
 #[poise::command(
     slash_command,
     required_permissions = "SEND_MESSAGES | MANAGE_MESSAGES",
-    required_bot_permissions = "SEND_MESSAGES | MANAGE_MESSAGES"
+    required_bot_permissions = "SEND_MESSAGES | MANAGE_MESSAGES",
+    description_localized("en-US", "Lists the scheduled menus of the channel"),
+    description_localized("en-GB", "Lists the scheduled menus of the channel")
 )]
 pub async fn list_scheduled(ctx: Context<'_>) -> Result<(), Error> {
     ctx.defer_ephemeral().await?;
 
     let channel_id = ctx.channel_id().get();
+    let t = current_view(ctx).await.locale.strings();
 
     let jobs = ctx.data().job_uuids.lock().await;
     fn fmt_job(job: &DataJob) -> String {
@@ -82,7 +108,7 @@ pub async fn list_scheduled(ctx: Context<'_>) -> Result<(), Error> {
         .join("\n");
 
     if jobs.is_empty() {
-        jobs = "Ei ajastettuja ruokalistoja".into();
+        jobs = t.no_schedules.into();
     }
 
     ctx.send(CreateReply::default().content(jobs).ephemeral(true))
@@ -91,14 +117,20 @@ pub async fn list_scheduled(ctx: Context<'_>) -> Result<(), Error> {
     Ok(())
 }
 
+/// Poistaa ajastetun ruokalistan
 #[poise::command(
     slash_command,
     required_permissions = "SEND_MESSAGES | MANAGE_MESSAGES",
-    required_bot_permissions = "SEND_MESSAGES | MANAGE_MESSAGES"
+    required_bot_permissions = "SEND_MESSAGES | MANAGE_MESSAGES",
+    description_localized("en-US", "Deletes a scheduled menu"),
+    description_localized("en-GB", "Deletes a scheduled menu")
 )]
 pub async fn delete_scheduled(
     ctx: Context<'_>,
-    #[description = "Uuid of scheduled job"] uuid: String,
+    #[description = "Ajastuksen uuid"]
+    #[description_localized("en-US", "Uuid of the scheduled menu")]
+    #[description_localized("en-GB", "Uuid of the scheduled menu")]
+    uuid: String,
 ) -> Result<(), Error> {
     ctx.defer_ephemeral().await?;
 
@@ -116,11 +148,11 @@ pub async fn delete_scheduled(
 
     save_jobs(ctx).await?;
 
+    let t = current_view(ctx).await.locale.strings();
+
     ctx.send(
         CreateReply::default()
-            .content(format!(
-                "Poistettu ajastettu ruokalista `{uuid}` onnistuneesti"
-            ))
+            .content(format!("{} `{uuid}`", t.schedule_deleted))
             .ephemeral(true),
     )
     .await?;
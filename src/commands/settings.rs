@@ -0,0 +1,273 @@
+use crate::Context;
+use crate::Error;
+use crate::locale::Locale;
+use crate::menu::CourseOrder;
+use crate::menu::PriceTier;
+use crate::provider::RestaurantId;
+use crate::restaurants::autocomplete_restaurant;
+use crate::restaurants::fetch_restaurant;
+use crate::settings::current_price_tier;
+use crate::settings::current_restaurant;
+use crate::settings::current_view;
+use poise::ChoiceParameter;
+use poise::CreateReply;
+
+/// Vaihtaa ravintolan, jonka ruokalistoja palvelimella näytetään
+///
+/// Ilman ravintolaa näyttää nykyisen ravintolan.
+/// Jos `kanava` on päällä, ravintola asetetaan vain tälle kanavalle,
+/// ja ilman ravintolaa kanavan oma asetus poistetaan.
+#[poise::command(
+    slash_command,
+    rename = "ravintola",
+    required_permissions = "SEND_MESSAGES | MANAGE_MESSAGES",
+    name_localized("en-US", "restaurant"),
+    description_localized("en-US", "Changes the restaurant whose menus are shown on the server"),
+    name_localized("en-GB", "restaurant"),
+    description_localized("en-GB", "Changes the restaurant whose menus are shown on the server")
+)]
+pub async fn set_restaurant(
+    ctx: Context<'_>,
+    #[description = "Ravintolan numero, muiden kuin Sodexon ravintoloille esim. compass:3087"]
+    #[name_localized("en-US", "restaurant")]
+    #[name_localized("en-GB", "restaurant")]
+    #[description_localized(
+        "en-US",
+        "Restaurant number, for other caterers than Sodexo e.g. compass:3087"
+    )]
+    #[description_localized(
+        "en-GB",
+        "Restaurant number, for other caterers than Sodexo e.g. compass:3087"
+    )]
+    #[autocomplete = "autocomplete_restaurant"]
+    ravintola: Option<String>,
+    #[description = "Koskee vain tätä kanavaa"]
+    #[name_localized("en-US", "channel")]
+    #[name_localized("en-GB", "channel")]
+    #[description_localized("en-US", "Only for this channel")]
+    #[description_localized("en-GB", "Only for this channel")]
+    kanava: Option<bool>,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let t = current_view(ctx).await.locale.strings();
+    let channel_only = kanava.unwrap_or(false);
+    let channel_id = ctx.channel_id().get();
+    let guild_id = ctx.guild_id().map(|g| g.get());
+
+    let ravintola = ravintola.map(|r| r.parse::<RestaurantId>()).transpose()?;
+
+    // makes sure the restaurant exists and adds it to the directory
+    if let Some(id) = &ravintola {
+        let restaurant = fetch_restaurant(id).await?;
+
+        let mut directory = ctx.data().restaurants.lock().await;
+        directory.insert(restaurant);
+        directory.save()?;
+    }
+
+    let msg = match (ravintola, channel_only) {
+        (None, false) => {
+            let id = current_restaurant(ctx).await;
+
+            match ctx.data().restaurants.lock().await.get(&id) {
+                Some(r) => format!("{} {r}", t.current_restaurant),
+                None => format!("{} `{id}`", t.current_restaurant),
+            }
+        }
+        (restaurant, true) => {
+            let mut settings = ctx.data().settings.lock().await;
+            settings.channels.entry(channel_id).or_default().restaurant = restaurant.clone();
+            settings.save()?;
+
+            match restaurant {
+                Some(r) => format!("{} `{r}`", t.channel_restaurant_set),
+                None => t.channel_restaurant_removed.to_string(),
+            }
+        }
+        (Some(restaurant), false) => {
+            let guild_id = guild_id.ok_or(t.guild_settings_only)?;
+
+            let mut settings = ctx.data().settings.lock().await;
+            settings.guilds.entry(guild_id).or_default().restaurant = Some(restaurant.clone());
+            settings.save()?;
+
+            format!("{} `{restaurant}`", t.guild_restaurant_set)
+        }
+    };
+
+    ctx.send(CreateReply::default().content(msg).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Vaihtaa järjestyksen, jossa palvelimen ruokalistojen ruoat näytetään
+#[poise::command(
+    slash_command,
+    rename = "jarjestys",
+    guild_only,
+    required_permissions = "SEND_MESSAGES | MANAGE_MESSAGES",
+    name_localized("en-US", "order"),
+    description_localized(
+        "en-US",
+        "Changes the order the dishes of the server's menus are shown in"
+    ),
+    name_localized("en-GB", "order"),
+    description_localized(
+        "en-GB",
+        "Changes the order the dishes of the server's menus are shown in"
+    )
+)]
+pub async fn set_course_order(
+    ctx: Context<'_>,
+    #[description = "Ruokien järjestys"]
+    #[name_localized("en-US", "order")]
+    #[name_localized("en-GB", "order")]
+    #[description_localized("en-US", "Order of the dishes")]
+    #[description_localized("en-GB", "Order of the dishes")]
+    jarjestys: CourseOrder,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let guild_id = ctx.guild_id().ok_or("not in a guild")?.get();
+
+    {
+        let mut settings = ctx.data().settings.lock().await;
+        settings.guilds.entry(guild_id).or_default().course_order = Some(jarjestys);
+        settings.save()?;
+    }
+
+    let locale = current_view(ctx).await.locale;
+
+    ctx.send(
+        CreateReply::default()
+            .content(format!(
+                "{} {}",
+                locale.strings().course_order_set,
+                jarjestys.label(locale)
+            ))
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Vaihtaa kielen, jolla ajastetut ruokalistat näytetään
+///
+/// Komennot näytetään aina käyttäjän omalla Discordin kielellä, jos sille on käännös.
+#[poise::command(
+    slash_command,
+    rename = "kieli",
+    guild_only,
+    required_permissions = "SEND_MESSAGES | MANAGE_MESSAGES",
+    name_localized("en-US", "language"),
+    description_localized("en-US", "Changes the language scheduled menus are shown in"),
+    name_localized("en-GB", "language"),
+    description_localized("en-GB", "Changes the language scheduled menus are shown in")
+)]
+pub async fn set_locale(
+    ctx: Context<'_>,
+    #[description = "Kieli"]
+    #[name_localized("en-US", "language")]
+    #[name_localized("en-GB", "language")]
+    #[description_localized("en-US", "Language")]
+    #[description_localized("en-GB", "Language")]
+    kieli: Locale,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let guild_id = ctx.guild_id().ok_or("not in a guild")?.get();
+
+    {
+        let mut settings = ctx.data().settings.lock().await;
+        settings.guilds.entry(guild_id).or_default().locale = Some(kieli);
+        settings.save()?;
+    }
+
+    let t = current_view(ctx).await.locale.strings();
+
+    ctx.send(
+        CreateReply::default()
+            .content(format!("{} {}", t.locale_set, kieli.name()))
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Valitsee hintaluokan, jonka hinta ruokalistoissa näytetään
+///
+/// Ilman hintaluokkaa näyttää nykyisen valinnan.
+/// Jos `palvelin` on päällä, hintaluokka asetetaan koko palvelimen oletukseksi,
+/// mikä vaatii viestien hallintaoikeuden.
+#[poise::command(
+    slash_command,
+    rename = "hintaluokka",
+    name_localized("en-US", "price-tier"),
+    description_localized("en-US", "Chooses the price tier shown on the menus"),
+    name_localized("en-GB", "price-tier"),
+    description_localized("en-GB", "Chooses the price tier shown on the menus")
+)]
+pub async fn set_price_tier(
+    ctx: Context<'_>,
+    #[description = "Hintaluokka"]
+    #[name_localized("en-US", "tier")]
+    #[name_localized("en-GB", "tier")]
+    #[description_localized("en-US", "Price tier")]
+    #[description_localized("en-GB", "Price tier")]
+    hintaluokka: Option<PriceTier>,
+    #[description = "Aseta palvelimen oletukseksi"]
+    #[name_localized("en-US", "server")]
+    #[name_localized("en-GB", "server")]
+    #[description_localized("en-US", "Set as the server's default")]
+    #[description_localized("en-GB", "Set as the server's default")]
+    palvelin: Option<bool>,
+) -> Result<(), Error> {
+    ctx.defer_ephemeral().await?;
+
+    let locale = current_view(ctx).await.locale;
+    let t = locale.strings();
+    let msg = match (hintaluokka, palvelin.unwrap_or(false)) {
+        (None, _) => match current_price_tier(ctx).await {
+            Some(tier) => format!("{} {}", t.current_price_tier, tier.label(locale)),
+            None => t.no_price_tier.to_string(),
+        },
+        (Some(tier), false) => {
+            let mut settings = ctx.data().settings.lock().await;
+            settings
+                .users
+                .entry(ctx.author().id.get())
+                .or_default()
+                .price_tier = Some(tier);
+            settings.save()?;
+
+            format!("{} {}", t.price_tier_set, tier.label(locale))
+        }
+        (Some(tier), true) => {
+            let guild_id = ctx.guild_id().ok_or(t.guild_settings_only)?.get();
+
+            let can_manage = ctx
+                .author_member()
+                .await
+                .and_then(|m| m.permissions)
+                .is_some_and(|p| p.manage_messages());
+            if !can_manage {
+                return Err(t.price_tier_needs_permission.into());
+            }
+
+            let mut settings = ctx.data().settings.lock().await;
+            settings.guilds.entry(guild_id).or_default().price_tier = Some(tier);
+            settings.save()?;
+
+            format!("{} {}", t.guild_price_tier_set, tier.label(locale))
+        }
+    };
+
+    ctx.send(CreateReply::default().content(msg).ephemeral(true))
+        .await?;
+
+    Ok(())
+}
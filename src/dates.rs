@@ -0,0 +1,357 @@
+use crate::Error;
+use crate::locale::Locale;
+use chrono::Datelike;
+use chrono::Days;
+use chrono::NaiveDate;
+use chrono::Weekday;
+
+/// Finnish weekday names as they appear in sodexo's weekly menus, and their english counterparts
+const WEEKDAYS: [(Weekday, &str, &str); 7] = [
+    (Weekday::Mon, "maanantai", "monday"),
+    (Weekday::Tue, "tiistai", "tuesday"),
+    (Weekday::Wed, "keskiviikko", "wednesday"),
+    (Weekday::Thu, "torstai", "thursday"),
+    (Weekday::Fri, "perjantai", "friday"),
+    (Weekday::Sat, "lauantai", "saturday"),
+    (Weekday::Sun, "sunnuntai", "sunday"),
+];
+
+/// Short forms people type, `ti` and `fri`
+const WEEKDAY_ABBREVIATIONS: [(Weekday, &str, &str); 7] = [
+    (Weekday::Mon, "ma", "mon"),
+    (Weekday::Tue, "ti", "tue"),
+    (Weekday::Wed, "ke", "wed"),
+    (Weekday::Thu, "to", "thu"),
+    (Weekday::Fri, "pe", "fri"),
+    (Weekday::Sat, "la", "sat"),
+    (Weekday::Sun, "su", "sun"),
+];
+
+/// Days relative to today and how many days away they are
+const RELATIVE_DAYS: [(&str, i64); 8] = [
+    ("eilen", -1),
+    ("yesterday", -1),
+    ("tänään", 0),
+    ("today", 0),
+    ("huomenna", 1),
+    ("tomorrow", 1),
+    ("ylihuomenna", 2),
+    ("day after tomorrow", 2),
+];
+
+pub fn parse_weekday(name: &str) -> Option<Weekday> {
+    let name = name.trim().to_lowercase();
+
+    WEEKDAYS
+        .iter()
+        .chain(WEEKDAY_ABBREVIATIONS.iter())
+        .find(|(_, fi, en)| *fi == name || *en == name)
+        .map(|(wd, _, _)| *wd)
+}
+
+/// Parses a day and month like `25.8.` and picks the year that puts the date closest to `today`
+pub fn parse_day_month(s: &str, today: NaiveDate) -> Result<NaiveDate, Error> {
+    let mut parts = s.trim().trim_end_matches('.').split('.');
+
+    let day: u32 = parts.next().ok_or("missing day")?.trim().parse()?;
+    let month: u32 = parts.next().ok_or("missing month")?.trim().parse()?;
+
+    if parts.next().is_some() {
+        return Err(format!("invalid date `{s}`").into());
+    }
+
+    [today.year() - 1, today.year(), today.year() + 1]
+        .into_iter()
+        .filter_map(|y| NaiveDate::from_ymd_opt(y, month, day))
+        .min_by_key(|d| (*d - today).num_days().abs())
+        .ok_or(format!("invalid date `{s}`").into())
+}
+
+/// Parses the date argument of the commands, accepts
+/// - an offset from today, `+1` or `-2`, or a word like `huomenna` or `tomorrow`
+/// - a weekday, `maanantai`, `ti` or `friday`, meaning the next one from today on
+/// - a finnish date, `24.12.` or `24.12.2025`
+/// - an ISO date, `2025-12-24`, or an ISO week date, `2025-W52-3` or `W52-3`
+pub fn parse_date(s: &str, today: NaiveDate) -> Result<NaiveDate, Error> {
+    let s = s.split_whitespace().collect::<Vec<&str>>().join(" ");
+    let lower = s.to_lowercase();
+
+    if s.starts_with(['+', '-']) {
+        let days: i64 = s
+            .parse()
+            .map_err(|_| format!("invalid day offset `{s}`, use a number like +1 or -1"))?;
+
+        return today
+            .checked_add_signed(chrono::Duration::days(days))
+            .ok_or(format!("invalid day offset `{s}`").into());
+    }
+
+    if let Some((_, days)) = RELATIVE_DAYS.iter().find(|(word, _)| *word == lower) {
+        return Ok(today + chrono::Duration::days(*days));
+    }
+
+    if let Some(weekday) = parse_weekday(&lower) {
+        return Ok(today + Days::new(weekday.days_since(today.weekday()).into()));
+    }
+
+    if let Some((week, day)) = lower.strip_prefix('w').and_then(|w| w.split_once('-')) {
+        return iso_week_day(today.iso_week().year(), week, day, &s);
+    }
+
+    if let Some((year, rest)) = lower.split_once("-w") {
+        let (week, day) = rest
+            .split_once('-')
+            .ok_or(format!("`{s}` is missing the day of the week, like {s}-1"))?;
+
+        return iso_week_day(
+            year.parse().map_err(|_| format!("invalid year in `{s}`"))?,
+            week,
+            day,
+            &s,
+        );
+    }
+
+    if s.contains('.') {
+        return match s.trim_end_matches('.').matches('.').count() {
+            1 => parse_day_month(&s, today),
+            _ => NaiveDate::parse_from_str(&s, "%d.%m.%Y")
+                .map_err(|_| format!("invalid date `{s}`, use a date like 24.12.").into()),
+        };
+    }
+
+    NaiveDate::parse_from_str(&s, "%Y-%m-%d").map_err(|_| {
+        format!("invalid date `{s}`, use a date like 24.12. or 2025-12-24, a weekday or an offset like +1").into()
+    })
+}
+
+fn iso_week_day(year: i32, week: &str, day: &str, s: &str) -> Result<NaiveDate, Error> {
+    let week: u32 = week.parse().map_err(|_| format!("invalid week in `{s}`"))?;
+    let day = day
+        .parse::<u32>()
+        .ok()
+        .filter(|d| (1..=7).contains(d))
+        .and_then(|d| Weekday::try_from(d as u8 - 1).ok())
+        .ok_or(format!("invalid day of the week in `{s}`, use 1 to 7"))?;
+
+    NaiveDate::from_isoywd_opt(year, week, day)
+        .ok_or(format!("year {year} has no week {week}").into())
+}
+
+/// Parses a `timeperiod` like `25.8. - 31.8.` or `29.12. - 4.1.` into its first and last day
+pub fn parse_timeperiod(s: &str, today: NaiveDate) -> Result<(NaiveDate, NaiveDate), Error> {
+    let (start, end) = s
+        .split_once('-')
+        .ok_or(format!("invalid timeperiod `{s}`"))?;
+
+    let start = parse_day_month(start, today)?;
+    let end = parse_day_month(end, start)?;
+
+    if end < start {
+        return Err(format!("timeperiod `{s}` ends before it starts").into());
+    }
+
+    Ok((start, end))
+}
+
+/// The date of `weekday` in the week starting at `start`
+pub fn date_in_week(start: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let offset = weekday.days_since(start.weekday());
+
+    start + Days::new(offset.into())
+}
+
+/// Capitalized name of the weekday, like sodexo's `Maanantai`
+pub fn weekday_name(weekday: Weekday, locale: Locale) -> String {
+    let (_, fi, en) = WEEKDAYS[weekday.num_days_from_monday() as usize];
+
+    capitalize(match locale {
+        Locale::Fi => fi,
+        Locale::En => en,
+    })
+}
+
+/// Capitalized short name of the weekday, `Ti` or `Tue`
+pub fn weekday_short(weekday: Weekday, locale: Locale) -> String {
+    let (_, fi, en) = WEEKDAY_ABBREVIATIONS[weekday.num_days_from_monday() as usize];
+
+    capitalize(match locale {
+        Locale::Fi => fi,
+        Locale::En => en,
+    })
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .map(|c| c.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+/// Monday of the week `day` is in
+pub fn week_start(day: NaiveDate) -> NaiveDate {
+    day.week(Weekday::Mon).first_day()
+}
+
+/// Parses a week selector into the monday of that week, accepts
+/// - an offset from the current week, `+1` or `-1`
+/// - an ISO week number of the current year, `42`, or of any year, `2025-W42`
+/// - any date within the week, `2025-10-15`
+pub fn parse_week(s: &str, today: NaiveDate) -> Result<NaiveDate, Error> {
+    let s = s.trim();
+
+    if s.starts_with(['+', '-']) {
+        let weeks: i64 = s.parse()?;
+
+        return week_start(today)
+            .checked_add_signed(chrono::Duration::weeks(weeks))
+            .ok_or(format!("invalid week offset `{s}`").into());
+    }
+
+    if let Some((year, week)) = s.split_once("-W").or(s.split_once("-w"))
+        && let Ok(week) = week.parse()
+    {
+        return iso_week(year.parse()?, week);
+    }
+
+    if let Ok(week) = s.parse::<u32>() {
+        return iso_week(today.iso_week().year(), week);
+    }
+
+    match parse_date(s, today) {
+        Ok(date) => Ok(week_start(date)),
+        Err(_) => Err(format!(
+            "invalid week `{s}`, use a week number, an offset like +1 or a date"
+        )
+        .into()),
+    }
+}
+
+fn iso_week(year: i32, week: u32) -> Result<NaiveDate, Error> {
+    NaiveDate::from_isoywd_opt(year, week, Weekday::Mon)
+        .ok_or(format!("year {year} has no week {week}").into())
+}
+
+#[test]
+fn weekdays() {
+    assert_eq!(parse_weekday("Maanantai"), Some(Weekday::Mon));
+    assert_eq!(parse_weekday(" KESKIVIIKKO "), Some(Weekday::Wed));
+    assert_eq!(parse_weekday("friday"), Some(Weekday::Fri));
+    assert_eq!(parse_weekday("ti"), Some(Weekday::Tue));
+    assert_eq!(parse_weekday("Fri"), Some(Weekday::Fri));
+    assert_eq!(parse_weekday("perjantaina"), None);
+}
+
+#[test]
+fn dates() {
+    let d = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).expect("invalid date");
+    // a friday
+    let today = d(2025, 10, 17);
+    let date = |s| parse_date(s, today).ok();
+
+    assert_eq!(date("+0"), Some(today));
+    assert_eq!(date("+3"), Some(d(2025, 10, 20)));
+    assert_eq!(date("-1"), Some(d(2025, 10, 16)));
+    assert_eq!(date("Huomenna"), Some(d(2025, 10, 18)));
+    assert_eq!(date("ylihuomenna"), Some(d(2025, 10, 19)));
+    assert_eq!(date("tomorrow"), Some(d(2025, 10, 18)));
+    assert_eq!(date("day  after tomorrow"), Some(d(2025, 10, 19)));
+    assert_eq!(date("eilen"), Some(d(2025, 10, 16)));
+
+    // weekdays are the next one, today included
+    assert_eq!(date("maanantai"), Some(d(2025, 10, 20)));
+    assert_eq!(date("ti"), Some(d(2025, 10, 21)));
+    assert_eq!(date("friday"), Some(today));
+    assert_eq!(date("to"), Some(d(2025, 10, 23)));
+
+    assert_eq!(date("24.12."), Some(d(2025, 12, 24)));
+    assert_eq!(date("2.1"), Some(d(2026, 1, 2)));
+    assert_eq!(date("24.12.2024"), Some(d(2024, 12, 24)));
+    assert_eq!(date("2025-12-24"), Some(d(2025, 12, 24)));
+    assert_eq!(date("2025-W52-3"), Some(d(2025, 12, 24)));
+    assert_eq!(date("w43-1"), Some(d(2025, 10, 20)));
+
+    for invalid in [
+        "",
+        "+",
+        "+x",
+        "32.12.",
+        "2025-W54-1",
+        "2025-W52-8",
+        "2025-W52",
+        "ensi viikko",
+    ] {
+        assert!(parse_date(invalid, today).is_err(), "{invalid}");
+    }
+}
+
+#[test]
+fn timeperiods() {
+    let d = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).expect("invalid date");
+
+    assert_eq!(
+        parse_timeperiod("25.8. - 31.8.", d(2025, 8, 27)).ok(),
+        Some((d(2025, 8, 25), d(2025, 8, 31)))
+    );
+
+    // across a month
+    assert_eq!(
+        parse_timeperiod("29.9. - 5.10.", d(2025, 10, 2)).ok(),
+        Some((d(2025, 9, 29), d(2025, 10, 5)))
+    );
+
+    // across a year, seen from both sides
+    assert_eq!(
+        parse_timeperiod("29.12. - 4.1.", d(2026, 1, 2)).ok(),
+        Some((d(2025, 12, 29), d(2026, 1, 4)))
+    );
+    assert_eq!(
+        parse_timeperiod("29.12. - 4.1.", d(2025, 12, 30)).ok(),
+        Some((d(2025, 12, 29), d(2026, 1, 4)))
+    );
+
+    // next week's menu fetched at the end of the year
+    assert_eq!(
+        parse_timeperiod("5.1. - 11.1.", d(2025, 12, 31)).ok(),
+        Some((d(2026, 1, 5), d(2026, 1, 11)))
+    );
+
+    assert!(parse_timeperiod("25.8.", d(2025, 8, 27)).is_err());
+    assert!(parse_timeperiod("32.8. - 31.8.", d(2025, 8, 27)).is_err());
+}
+
+#[test]
+fn dates_in_week() {
+    let d = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).expect("invalid date");
+
+    assert_eq!(date_in_week(d(2025, 8, 25), Weekday::Mon), d(2025, 8, 25));
+    assert_eq!(date_in_week(d(2025, 8, 25), Weekday::Fri), d(2025, 8, 29));
+    assert_eq!(date_in_week(d(2025, 12, 29), Weekday::Fri), d(2026, 1, 2));
+}
+
+#[test]
+fn weeks() {
+    let d = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).expect("invalid date");
+    let today = d(2025, 10, 17);
+
+    assert_eq!(parse_week("+0", today).ok(), Some(d(2025, 10, 13)));
+    assert_eq!(parse_week("+1", today).ok(), Some(d(2025, 10, 20)));
+    assert_eq!(parse_week("-1", today).ok(), Some(d(2025, 10, 6)));
+    assert_eq!(parse_week("42", today).ok(), Some(d(2025, 10, 13)));
+    assert_eq!(parse_week("2026-W1", today).ok(), Some(d(2025, 12, 29)));
+    assert_eq!(parse_week("2025-10-22", today).ok(), Some(d(2025, 10, 20)));
+
+    assert!(parse_week("54", today).is_err());
+    assert!(parse_week("ensi viikko", today).is_err());
+
+    // everything that is a date works too
+    assert_eq!(parse_week("huomenna", today).ok(), Some(d(2025, 10, 13)));
+    assert_eq!(parse_week("24.10.", today).ok(), Some(d(2025, 10, 20)));
+    assert_eq!(parse_week("2025-W43-5", today).ok(), Some(d(2025, 10, 20)));
+
+    assert_eq!(weekday_name(Weekday::Mon, Locale::Fi), "Maanantai");
+    assert_eq!(weekday_name(Weekday::Sun, Locale::Fi), "Sunnuntai");
+    assert_eq!(weekday_name(Weekday::Wed, Locale::En), "Wednesday");
+    assert_eq!(weekday_short(Weekday::Tue, Locale::Fi), "Ti");
+    assert_eq!(weekday_short(Weekday::Tue, Locale::En), "Tue");
+}
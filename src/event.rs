@@ -2,12 +2,14 @@ use poise::serenity_prelude as serenity;
 use ::serenity::all::Interaction;
 use crate::{Error, Data};
 use crate::list::extra_info::extra_info;
+use crate::list::translate::translate;
+use crate::list::week::week_page;
 
 pub async fn event_handler(
     ctx: &serenity::Context,
     event: &serenity::FullEvent,
     _framework: poise::FrameworkContext<'_, Data, Error>,
-    _data: &Data,
+    data: &Data,
 ) -> Result<(), Error> {
     if let serenity::FullEvent::InteractionCreate { interaction } = event
         && let Interaction::Component(c) = interaction
@@ -15,7 +17,11 @@ pub async fn event_handler(
         let id = &c.data.custom_id;
 
         if id.starts_with("infoday") {
-            extra_info(ctx, c).await?;
+            extra_info(ctx, c, data).await?;
+        } else if id.starts_with("weekpage") {
+            week_page(ctx, c, data).await?;
+        } else if id.starts_with("translate") {
+            translate(ctx, c, data).await?;
         }
     }
 
@@ -0,0 +1,474 @@
+use crate::Data;
+use crate::Error;
+use crate::cache::cached_day;
+use crate::dates::weekday_short;
+use crate::list::truncate;
+use crate::locale::Locale;
+use crate::menu::Dish;
+use crate::provider::RestaurantId;
+use chrono::Datelike;
+use chrono::Local;
+use chrono::NaiveDate;
+use chrono_tz::Europe::Helsinki;
+use poise::serenity_prelude as serenity;
+use serde::Deserialize;
+use serde::Serialize;
+use serenity::all::ButtonStyle;
+use serenity::all::CreateActionRow;
+use serenity::all::CreateButton;
+use serenity::all::CreateMessage;
+use serenity::all::ReactionType;
+use serenity::all::UserId;
+use std::collections::HashMap;
+use std::fs::read_to_string;
+use std::fs::write;
+use std::sync::Arc;
+use tokio_cron_scheduler::Job;
+use tokio_cron_scheduler::JobSchedulerError;
+use tokio_cron_scheduler::job::JobLocked;
+
+const FOLLOWS_FILE: &str = "follows.json";
+
+/// Every morning, the coming days are checked for followed dishes
+pub const FOLLOW_CRON: &str = "0 0 8 * * *";
+
+/// How many serving days ahead followers are told about
+const DAYS_AHEAD: usize = 5;
+
+/// Discord allows 5 rows of 5 buttons
+const BUTTONS_MAX: usize = 25;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum FollowKind {
+    /// Matched loosely against the dish's titles
+    Dish,
+    /// Matched anywhere in the dish's name or ingredients, like in [`crate::profile::Profile`]
+    Ingredient,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Follow {
+    pub kind: FollowKind,
+    /// Lowercase, as the user wrote it
+    pub query: String,
+    pub restaurant: RestaurantId,
+}
+
+impl Follow {
+    pub fn matches(&self, dish: &Dish) -> bool {
+        match self.kind {
+            FollowKind::Dish => [Some(&dish.title_fi), dish.title_en.as_ref()]
+                .into_iter()
+                .flatten()
+                .any(|title| fuzzy_match(&self.query, title)),
+            FollowKind::Ingredient => dish.names().iter().any(|n| n.contains(&self.query)),
+        }
+    }
+
+    pub fn describe(&self, locale: Locale) -> String {
+        let t = locale.strings();
+        let kind = match self.kind {
+            FollowKind::Dish => t.followed_dish,
+            FollowKind::Ingredient => t.followed_ingredient,
+        };
+
+        format!(
+            "{kind} `{}` {} `{}`",
+            self.query, t.in_restaurant, self.restaurant
+        )
+    }
+}
+
+/// A user's follows, `notified` has the dishes they've already been told about
+/// as `{restaurant}_{day}_{dish}`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Follower {
+    /// The language the user last followed something in, DMs have no other to go by
+    pub locale: Locale,
+    pub follows: Vec<Follow>,
+    pub notified: Vec<String>,
+}
+
+impl Follower {
+    /// Index of a follow by its number in the list, starting from 1, by what is
+    /// followed or by how it's described in either language
+    pub fn find(&self, s: &str) -> Option<usize> {
+        let s = s.trim().to_lowercase();
+
+        if let Ok(n) = s.parse::<usize>() {
+            return n.checked_sub(1).filter(|i| *i < self.follows.len());
+        }
+
+        self.follows.iter().position(|f| {
+            f.query == s
+                || [Locale::Fi, Locale::En]
+                    .iter()
+                    .any(|l| f.describe(*l).to_lowercase() == s)
+        })
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Follows {
+    #[serde(default)]
+    pub users: HashMap<u64, Follower>,
+}
+
+impl Follows {
+    pub fn load() -> Result<Self, Error> {
+        let follows =
+            serde_json::from_str(&read_to_string(FOLLOWS_FILE).unwrap_or("{}".to_string()))?;
+
+        Ok(follows)
+    }
+
+    pub fn save(&self) -> Result<(), Error> {
+        let data = serde_json::to_string(self)?;
+        write(FOLLOWS_FILE, data)?;
+
+        Ok(())
+    }
+
+    /// Every restaurant someone follows something in
+    fn restaurants(&self) -> Vec<RestaurantId> {
+        let mut restaurants = self
+            .users
+            .values()
+            .flat_map(|u| u.follows.iter().map(|f| f.restaurant.clone()))
+            .collect::<Vec<RestaurantId>>();
+        restaurants.sort();
+        restaurants.dedup();
+
+        restaurants
+    }
+}
+
+/// Edits needed to turn `a` into `b`, swapping two letters counts as one
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.chars().collect::<Vec<char>>();
+    let b = b.chars().collect::<Vec<char>>();
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    d[0] = (0..=b.len()).collect();
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+
+    d[a.len()][b.len()]
+}
+
+/// Whether every word of `query` is in `title`, allowing for a typo or two in
+/// longer words. Finnish compounds are matched from the start too, so
+/// `tonikala` is found in `tonnikalapatonki`.
+pub fn fuzzy_match(query: &str, title: &str) -> bool {
+    let title = title.to_lowercase();
+    let words = title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<&str>>();
+
+    query
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|q| !q.is_empty())
+        .all(|q| {
+            let len = q.chars().count();
+            let allowed = match len {
+                n if n >= 9 => 2,
+                n if n >= 5 => 1,
+                _ => 0,
+            };
+
+            words.iter().any(|w| {
+                w.contains(q)
+                    || (len - allowed..=len + allowed).any(|n| {
+                        let prefix = w.chars().take(n).collect::<String>();
+                        edit_distance(q, &prefix) <= allowed
+                    })
+            })
+        })
+}
+
+/// The followed dishes on `day`'s menu, by user, leaving out the ones they've
+/// been told about
+pub fn new_matches<'a>(
+    follows: &Follows,
+    restaurant: &RestaurantId,
+    day: &str,
+    dishes: &'a [Dish],
+) -> HashMap<u64, Vec<(&'a Dish, String)>> {
+    let mut matches: HashMap<u64, Vec<(&Dish, String)>> = HashMap::new();
+
+    for (user, follower) in &follows.users {
+        for dish in dishes {
+            let key = format!("{restaurant}_{day}_{}", dish.id);
+            if follower.notified.contains(&key) {
+                continue;
+            }
+
+            if let Some(follow) = follower
+                .follows
+                .iter()
+                .find(|f| &f.restaurant == restaurant && f.matches(dish))
+            {
+                matches
+                    .entry(*user)
+                    .or_default()
+                    .push((dish, follow.query.clone()));
+            }
+        }
+    }
+
+    matches
+}
+
+/// A DM listing the followed dishes of one day, with buttons to their info views
+fn fmt_notification(
+    restaurant: &RestaurantId,
+    date: NaiveDate,
+    title: &str,
+    url: &str,
+    dishes: &[(&Dish, String)],
+    locale: Locale,
+) -> CreateMessage {
+    let day = date.format("%Y-%m-%d");
+    let list = dishes
+        .iter()
+        .map(|(d, query)| format!("- {} (`{query}`)\n", d.title(locale)))
+        .collect::<String>();
+
+    let buttons = dishes
+        .iter()
+        .take(BUTTONS_MAX)
+        .map(|(d, _)| {
+            CreateButton::new(format!("infoday_{restaurant}_{day}_{}", d.id))
+                .emoji(ReactionType::Unicode("ℹ️".to_string()))
+                .label(truncate(d.title(locale), 80))
+                .style(ButtonStyle::Secondary)
+        })
+        .collect::<Vec<CreateButton>>();
+
+    CreateMessage::default()
+        .content(format!(
+            "# 🔔 {}\n**{} {}** [{title}](<{url}>)\n{list}",
+            locale.strings().followed_on_menu,
+            weekday_short(date.weekday(), locale),
+            date.format("%d.%m.")
+        ))
+        .components(
+            buttons
+                .chunks(5)
+                .map(|row| CreateActionRow::Buttons(row.to_vec()))
+                .collect(),
+        )
+}
+
+/// Checks the coming serving days of every followed restaurant and DMs the
+/// followers of dishes on them
+pub async fn notify_followers(ctx: &serenity::Context, data: &Data) -> Result<(), Error> {
+    let today = Local::now().date_naive();
+    let first = today.format("%Y-%m-%d").to_string();
+
+    let restaurants = {
+        let mut follows = data.follows.lock().await;
+
+        // days that have passed won't come up again
+        for follower in follows.users.values_mut() {
+            follower.notified.retain(|key| {
+                key.rsplit('_')
+                    .nth(1)
+                    .is_some_and(|day| day >= first.as_str())
+            });
+        }
+
+        follows.restaurants()
+    };
+
+    for restaurant in restaurants {
+        let days = data
+            .cache
+            .lock()
+            .await
+            .serving_days(&restaurant, today, DAYS_AHEAD)
+            .into_iter()
+            .map(|(date, _)| date)
+            .collect::<Vec<NaiveDate>>();
+
+        for date in days {
+            let day = date.format("%Y-%m-%d").to_string();
+
+            let menu = match cached_day(data, &restaurant, &day).await {
+                Ok(menu) => menu.menu,
+                Err(e) => {
+                    println!("unable to check follows for {restaurant} {day}: {e:?}");
+                    continue;
+                }
+            };
+
+            // messages are built under the lock but sent without it, so commands
+            // don't wait on discord
+            let pending = {
+                let follows = data.follows.lock().await;
+
+                new_matches(&follows, &restaurant, &day, &menu.dishes)
+                    .into_iter()
+                    .filter_map(|(user, dishes)| {
+                        let locale = follows.users.get(&user)?.locale;
+                        let message = fmt_notification(
+                            &restaurant,
+                            date,
+                            &menu.title,
+                            &menu.url,
+                            &dishes,
+                            locale,
+                        );
+                        let keys = dishes
+                            .iter()
+                            .map(|(d, _)| format!("{restaurant}_{day}_{}", d.id))
+                            .collect::<Vec<String>>();
+
+                        Some((user, message, keys))
+                    })
+                    .collect::<Vec<_>>()
+            };
+
+            let mut sent = Vec::new();
+            for (user, message, keys) in pending {
+                match UserId::new(user).direct_message(&ctx.http, message).await {
+                    Ok(_) => sent.push((user, keys)),
+                    Err(e) => println!("unable to message {user}: {e:?}"),
+                }
+            }
+
+            let mut follows = data.follows.lock().await;
+            for (user, keys) in sent {
+                // the user may have stopped following meanwhile
+                if let Some(follower) = follows.users.get_mut(&user) {
+                    follower.notified.extend(keys);
+                }
+            }
+        }
+    }
+
+    data.follows.lock().await.save()
+}
+
+pub fn create_follow_job(
+    ctx: &serenity::Context,
+    data: Data,
+) -> Result<JobLocked, JobSchedulerError> {
+    let ctx = Arc::new(ctx.clone());
+
+    Job::new_async_tz(FOLLOW_CRON, Helsinki, move |_uuid, _l| {
+        let ctx = ctx.clone();
+        let data = data.clone();
+        Box::pin(async move {
+            if let Err(e) = notify_followers(&ctx, &data).await {
+                println!("Error notifying followers {e:#?}");
+            }
+        })
+    })
+}
+
+#[test]
+fn fuzzy_titles() {
+    assert!(fuzzy_match("tonnikala", "Tonnikalapatonki"));
+    assert!(fuzzy_match("tonikala", "Tonnikalapatonki"));
+    assert!(fuzzy_match("Falafel", "Falafel-papupyttipannua"));
+    assert!(fuzzy_match(
+        "falafell pyttipannu",
+        "Falafel-papupyttipannua"
+    ));
+    assert!(fuzzy_match("lasagne", "Jauhelihalasagnettea"));
+    assert!(fuzzy_match("lasange", "Lasagne"));
+
+    assert!(!fuzzy_match("kala", "Kanakeitto"));
+    assert!(!fuzzy_match("tonnikala", "Tomaattikeittoa"));
+    assert!(!fuzzy_match("falafel kana", "Falafel-papupyttipannua"));
+}
+
+#[test]
+fn followed_dishes() {
+    use crate::menu::Menu;
+    use crate::types::day::DailyMenu;
+
+    let daily = std::fs::read_to_string("tests/2025-09-02.json").expect("no file");
+    let menu: DailyMenu = serde_json::from_str(&daily).expect("unable to parse json");
+    let menu: Menu = menu.into();
+    let restaurant = RestaurantId::default();
+
+    let follow = |kind, query: &str| Follow {
+        kind,
+        query: query.to_string(),
+        restaurant: restaurant.clone(),
+    };
+
+    let mut follows = Follows::default();
+    follows.users.insert(
+        1,
+        Follower {
+            follows: vec![
+                follow(FollowKind::Dish, "falafel"),
+                follow(FollowKind::Ingredient, "ananas"),
+            ],
+            ..Default::default()
+        },
+    );
+    follows.users.insert(
+        2,
+        Follower {
+            follows: vec![Follow {
+                restaurant: "compass:3087".parse().expect("unable to parse id"),
+                ..follow(FollowKind::Dish, "falafel")
+            }],
+            ..Default::default()
+        },
+    );
+
+    let matches = new_matches(&follows, &restaurant, "2025-09-02", &menu.dishes);
+    let titles = matches[&1]
+        .iter()
+        .map(|(d, _)| d.title_fi.as_str())
+        .collect::<Vec<&str>>();
+    assert_eq!(
+        titles,
+        vec!["Falafel-papupyttipannua", "Persikka-ananassmoothie"]
+    );
+    // follows are per restaurant
+    assert!(!matches.contains_key(&2));
+
+    // a dish is only told about once
+    let falafel = &matches[&1][0].0.id;
+    follows
+        .users
+        .get_mut(&1)
+        .expect("no follower")
+        .notified
+        .push(format!("{restaurant}_2025-09-02_{falafel}"));
+    let matches = new_matches(&follows, &restaurant, "2025-09-02", &menu.dishes);
+    assert_eq!(matches[&1].len(), 1);
+    let follower = &follows.users[&1];
+    assert_eq!(follower.find("2"), Some(1));
+    assert_eq!(follower.find("3"), None);
+    assert_eq!(follower.find("0"), None);
+    assert_eq!(follower.find(" Ananas "), Some(1));
+    assert_eq!(
+        follower.find(&follower.follows[0].describe(Locale::En)),
+        Some(0)
+    );
+    assert_eq!(follower.find("kana"), None);
+}
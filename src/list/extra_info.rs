@@ -1,41 +1,58 @@
 use crate::CreateInteractionResponseFollowup;
+use crate::Data;
 use crate::Error;
-use crate::list::Recipe;
-use crate::list::fetch_day;
+use crate::cache::cached_day;
+use crate::list::fmt_diet;
+use crate::list::truncate;
+use crate::locale::Locale;
+use crate::menu::Recipe;
+use crate::menu::allergen::Allergen;
+use crate::menu::ingredient::fmt_ingredients;
+use crate::profile::Conflict;
+use crate::profile::describe_conflicts;
+use crate::provider::RestaurantId;
+use crate::settings::user_view;
 use ::serenity::all::CreateEmbed;
 use poise::serenity_prelude as serenity;
 use serenity::all::ComponentInteraction;
+use serenity::all::ComponentInteractionDataKind;
 use serenity::all::CreateInteractionResponse;
 use serenity::all::CreateInteractionResponseMessage;
 
-fn fmt_recipe(recipe: &Recipe) -> String {
+/// Discord refuses embeds with longer descriptions
+const EMBED_DESCRIPTION_MAX: usize = 4096;
+
+fn fmt_recipe(recipe: &Recipe, locale: Locale) -> String {
     format!(
         r"
 ## {}
 
-### Ainesosat
-```
+### {}
 {}
-```
-### Ravintosisältö
+### {}
 ```
 {}
 ```
 ",
         recipe.name,
-        recipe.ingredients.to_string(),
+        locale.strings().ingredients,
+        match recipe.ingredients.is_empty() {
+            true => "N/A\n".to_string(),
+            false => fmt_ingredients(&recipe.ingredients),
+        },
+        locale.strings().nutrition,
         recipe
-            .nutrients
-            .split('|')
-            .map(str::trim_start)
-            .collect::<Vec<&str>>()
-            .join("\n")
+            .nutrition
+            .as_ref()
+            .map_or("N/A".to_string(), |n| n.table(locale))
+            .trim_end()
     )
 }
 
 pub async fn extra_info(
     ctx: &serenity::Context,
     interaction: &ComponentInteraction,
+    data: &Data,
 ) -> Result<(), Error> {
     let id = &interaction.data.custom_id;
     println!("{id}");
@@ -45,75 +62,128 @@ pub async fn extra_info(
     );
     interaction.create_response(&ctx.http, defer).await?;
 
+    // infoday_{restaurant}_{day}_{n}, buttons posted before restaurants were configurable
+    // are missing the restaurant and always refer to the default one. The week
+    // view's select menu has `select` for n and the dish as the selected value.
     let mut info: Vec<&str> = id.split('_').collect();
-    let n = info.pop().ok_or("cannot get n")?;
+    let n = match &interaction.data.kind {
+        ComponentInteractionDataKind::StringSelect { values } => {
+            info.pop();
+            values.first().ok_or("nothing selected")?
+        }
+        _ => info.pop().ok_or("cannot get n")?,
+    };
     let day = info.pop().ok_or("cannot get day")?;
-
-    let menu = fetch_day(day).await?;
-    let course = menu.courses.get(n).ok_or("invalid n")?.to_owned();
-
-    let allergens = course
-        .additional_diet_info
-        .allergens
-        .unwrap_or("N/A".to_string());
+    let restaurant = match info.pop() {
+        Some("infoday") | None => RestaurantId::default(),
+        Some(r) => r.parse()?,
+    };
+
+    let view = user_view(
+        data,
+        interaction.guild_id.map(|g| g.get()),
+        interaction.user.id.get(),
+        Some(&interaction.locale),
+    )
+    .await;
+    let locale = view.locale;
+    let t = locale.strings();
+
+    let menu = cached_day(data, &restaurant, day).await?;
+    let note = menu.outdated_note(locale).unwrap_or_default();
+    let course = menu.menu.dish(n).ok_or("invalid n")?;
+
+    // the ones the EU regulates first, then whatever else the caterer listed
+    let (eu14, other): (Vec<&Allergen>, Vec<&Allergen>) =
+        course.allergens.iter().partition(|a| a.kind.is_eu14());
+    let join = |a: Vec<&Allergen>| {
+        a.iter()
+            .map(|a| a.localized(locale))
+            .collect::<Vec<String>>()
+            .join(", ")
+    };
+
+    let allergens = match (eu14.is_empty(), other.is_empty()) {
+        (true, true) => "N/A".to_string(),
+        (false, true) => join(eu14),
+        (true, false) => format!("{}: {}", t.other_allergens, join(other)),
+        (false, false) => format!("{}\n{}: {}", join(eu14), t.other_allergens, join(other)),
+    };
+
+    let codes = course
+        .diet_codes
+        .iter()
+        .map(|c| format!("- `{c}` {}\n", c.description(locale)))
+        .collect::<String>();
+    let codes = match codes.is_empty() {
+        true => codes,
+        false => format!("# {}\n{codes}", t.diet_codes),
+    };
 
     let recipes = course
-        .recipes
-        .ok_or("invalid recipe json")?
         .recipes
         .iter()
-        .map(fmt_recipe)
+        .map(|r| fmt_recipe(r, locale))
         .collect::<String>();
 
-    let embed = CreateEmbed::default()
-        .title("Reseptit")
-        .description(recipes);
+    // a single recipe's nutrients are already in its own section
+    let total = match course.recipes.len() {
+        n if n > 1 => course
+            .nutrition()
+            .map(|n| format!("# {}\n```\n{}```", t.nutrition_total, n.table(locale)))
+            .unwrap_or_default(),
+        _ => String::new(),
+    };
+
+    // what the reader avoids goes first so it can't be missed
+    let (warnings, diet): (Vec<Conflict>, Vec<Conflict>) = view
+        .profile
+        .conflicts(course)
+        .into_iter()
+        .partition(Conflict::is_warning);
+    let (allergen_warnings, ingredient_warnings): (Vec<Conflict>, Vec<Conflict>) = warnings
+        .into_iter()
+        .partition(|c| matches!(c, Conflict::Allergen(_)));
+
+    let warn = |label: &str, conflicts: &[Conflict]| match conflicts.is_empty() {
+        true => String::new(),
+        false => format!(
+            "> ⚠️ **{label}:** {}\n",
+            describe_conflicts(conflicts, locale)
+        ),
+    };
+    let warnings = warn(t.avoided_allergens, &allergen_warnings)
+        + &warn(t.avoided_ingredients, &ingredient_warnings);
+
+    let diet = match diet.is_empty() {
+        true => String::new(),
+        false => format!(
+            "⛔ {}: {}\n",
+            t.not_for_you,
+            describe_conflicts(&diet, locale)
+        ),
+    };
 
-    let title = course.title_fi.unwrap_or("N/A".to_string());
-    let price = course.price.unwrap_or("N/A".to_string());
-    let diet_info = course.diet_info;
-    let food_info = course.additional_diet_info.food_info;
+    let embed = CreateEmbed::default()
+        .title(t.recipes)
+        .description(truncate(&recipes, EMBED_DESCRIPTION_MAX));
 
     let text = format!(
         r"
-# {title} - {price}
-
-- Gluteeniton {}
-- Laktoositon {}
-- Maidoton {}
-- Vähälaktoosinen {}
-
-- =< 0.5 kg CO2 Päästöt {}
-- Parempi Valinta {}
-- Vegaaninen {}
-- Opiskelijaruokailusuositusten mukainen {}
-
-- Sisältää porsaanlihaa {}
-- Liha Suomesta {}
-- Liha muualta EU:sta {}
-- Liha muualta {}
-# Allergeenit
-```
+{warnings}# {} - {}
+{note}
+{diet}
 {}
+{codes}# {}
+```
+{allergens}
 ```
+{total}
 ",
-        if diet_info.gluten_free { "✅" } else { "❌" },
-        if diet_info.lactose_free { "✅" } else { "❌" },
-        if diet_info.milk_free { "✅" } else { "❌" },
-        if diet_info.low_lactose { "✅" } else { "❌" },
-        if food_info.co2 { "✅" } else { "❌" },
-        if food_info.heart { "✅" } else { "❌" },
-        if food_info.vegan { "✅" } else { "❌" },
-        if food_info.student_recommendation {
-            "✅"
-        } else {
-            "❌"
-        },
-        if food_info.pork { "✅" } else { "❌" },
-        if food_info.fi_meat { "✅" } else { "❌" },
-        if food_info.eu_meat { "✅" } else { "❌" },
-        if food_info.other_meat { "✅" } else { "❌" },
-        allergens,
+        course.title(locale),
+        course.price.for_tier(view.tier, locale),
+        fmt_diet(&course.diet, locale),
+        t.allergens,
     );
 
     let followup = CreateInteractionResponseFollowup::new()
@@ -0,0 +1,75 @@
+use crate::list::truncate;
+use crate::locale::Locale;
+use crate::menu::Menu;
+use crate::profile::GroupFit;
+use crate::profile::describe_conflicts;
+
+/// Discord refuses messages with longer content
+const MESSAGE_MAX: usize = 2000;
+
+/// The dishes that suit everyone in the group, then the rest with who they
+/// don't suit and why. `unshared` are the members whose profiles weren't checked.
+pub fn fmt_group(
+    day: &str,
+    menu: &Menu,
+    members: &[u64],
+    unshared: &[u64],
+    fits: &[GroupFit],
+    locale: Locale,
+) -> String {
+    let t = locale.strings();
+
+    let mentions = |ids: &[u64]| {
+        ids.iter()
+            .map(|id| format!("<@{id}>"))
+            .collect::<Vec<String>>()
+            .join(", ")
+    };
+    let unshared = match unshared.is_empty() {
+        true => String::new(),
+        false => format!("{}: {}\n", t.no_shared_profile, mentions(unshared)),
+    };
+
+    let (suitable, other): (Vec<&GroupFit>, Vec<&GroupFit>) =
+        fits.iter().partition(|f| f.conflicts.is_empty());
+
+    let suitable = match suitable.is_empty() {
+        true => format!("{}\n", t.suits_no_one),
+        false => suitable
+            .iter()
+            .map(|f| format!("- {}\n", f.dish.title(locale)))
+            .collect(),
+    };
+
+    let other = other
+        .iter()
+        .map(|f| {
+            let reasons = f
+                .conflicts
+                .iter()
+                .map(|(id, c)| format!("  - <@{id}>: {}\n", describe_conflicts(c, locale)))
+                .collect::<String>();
+
+            format!("- {}\n{reasons}", f.dish.title(locale))
+        })
+        .collect::<String>();
+    let other = match other.is_empty() {
+        true => other,
+        false => format!("## ❌ {}\n{other}", t.suits_not_everyone),
+    };
+
+    let text = format!(
+        r"# [{}](<{}>) - {day}
+{}: {}
+{unshared}
+## ✅ {}
+{suitable}{other}",
+        menu.title,
+        menu.url,
+        t.group,
+        mentions(members),
+        t.suits_everyone
+    );
+
+    truncate(&text, MESSAGE_MAX)
+}
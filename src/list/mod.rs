@@ -1,79 +1,156 @@
-use crate::types::week::WeeklyMenu;
+use crate::locale::Locale;
+use crate::menu::Category;
+use crate::menu::DietFlags;
+use crate::menu::Dish;
+use crate::menu::Menu;
+use crate::profile::Conflict;
+use crate::profile::describe_conflicts;
+use crate::provider::RestaurantId;
+use crate::settings::View;
+use poise::ChoiceParameter;
 use poise::CreateReply;
+use serenity::all::ButtonStyle;
 use serenity::all::CreateButton;
 use serenity::all::ReactionType;
 use serenity::all::{Colour, CreateActionRow, CreateEmbed};
 
-use crate::{Error, types::common::Recipe};
-
 pub mod extra_info;
+pub mod group;
+pub mod translate;
+pub mod week;
+
+/// Cuts `s` to at most `max` characters, marking the cut with `...`
+pub fn truncate(s: &str, max: usize) -> String {
+    match s.chars().count() > max {
+        true => s.chars().take(max.saturating_sub(3)).collect::<String>() + "...",
+        false => s.to_string(),
+    }
+}
 
-use crate::types::{common::Course, day::DailyMenu};
+fn check(b: bool) -> &'static str {
+    if b { "✅" } else { "❌" }
+}
+
+/// The ✅/❌ list shared by the menu embeds and the info view
+pub fn fmt_diet(diet: &DietFlags, locale: Locale) -> String {
+    let t = locale.strings();
+
+    format!(
+        r"- {} {}
+- {} {}
+- {} {}
+- {} {}
+
+- {} {}
+- {} {}
+- {} {}
+- {} {}
+
+- {} {}
+- {} {}
+- {} {}
+- {} {}",
+        t.gluten_free,
+        check(diet.gluten_free),
+        t.lactose_free,
+        check(diet.lactose_free),
+        t.milk_free,
+        check(diet.milk_free),
+        t.low_lactose,
+        check(diet.low_lactose),
+        t.low_co2,
+        check(diet.low_co2),
+        t.better_choice,
+        check(diet.better_choice),
+        t.vegan,
+        check(diet.vegan),
+        t.student_recommendation,
+        check(diet.student_recommendation),
+        t.pork,
+        check(diet.pork),
+        t.fi_meat,
+        check(diet.fi_meat),
+        t.eu_meat,
+        check(diet.eu_meat),
+        t.other_meat,
+        check(diet.other_meat),
+    )
+}
 
-fn fmt_course(course: Course) -> CreateEmbed {
-    let title = course.title_fi.unwrap_or("N/A".to_string());
-    let price = course.price.unwrap_or("N/A".to_string());
+fn fmt_course(course: &Dish, view: &View) -> CreateEmbed {
+    let t = view.locale.strings();
+    let conflicts = view.profile.conflicts(course);
 
+    // only per user views have a profile, posts for everyone are never marked
+    let title = match conflicts.iter().any(Conflict::is_warning) {
+        true => format!("⚠️ {}", course.title(view.locale)),
+        false => course.title(view.locale).to_string(),
+    };
     let mut embed = CreateEmbed::new().title(title);
 
-    let category = course.category.unwrap_or("N/A".to_string());
-    let diet_info = course.diet_info;
-    let food_info = course.additional_diet_info.food_info;
-
-    if category.contains("VEGAN") {
-        embed = embed.color(Colour::DARK_GREEN);
-    } else if category.contains("bakery") {
-        embed = embed.color(Colour::ORANGE);
-    } else if category.contains("favorites") {
-        embed = embed.color(Colour::RED);
-    } else if category.contains("SOUP") {
-        embed = embed.color(Colour::TEAL);
-    } else if category.contains("SWEET") {
-        embed = embed.color(Colour::FABLED_PINK);
+    match course.category {
+        Category::Vegan => embed = embed.color(Colour::DARK_GREEN),
+        Category::Bakery => embed = embed.color(Colour::ORANGE),
+        Category::Main => embed = embed.color(Colour::RED),
+        Category::Soup => embed = embed.color(Colour::TEAL),
+        Category::Dessert => embed = embed.color(Colour::FABLED_PINK),
+        Category::Other => (),
     }
 
-    embed = embed.description(format!(
+    let codes = match course.diet_codes.is_empty() {
+        true => String::new(),
+        false => format!(
+            "{}: `{}`\n",
+            t.diet_codes,
+            course
+                .diet_codes
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+    };
+
+    let conflicts = match conflicts.is_empty() {
+        true => String::new(),
+        false => format!(
+            "⛔ {}: {}\n",
+            t.not_for_you,
+            describe_conflicts(&conflicts, view.locale)
+        ),
+    };
+
+    embed.description(format!(
         r"
-Hinta: `{price}`
-- Gluteeniton {}
-- Laktoositon {}
-- Maidoton {}
-- Vähälaktoosinen {}
-
-- =< 0.5 kg CO2 Päästöt {}
-- Parempi Valinta {}
-- Vegaaninen {}
-- Opiskelijaruokailusuositusten mukainen {}
-
-- Sisältää porsaanlihaa {}
-- Liha Suomesta {}
-- Liha muualta EU:sta {}
-- Liha muualta {}
+{conflicts}{}: `{}`
+{codes}{}
 ",
-        if diet_info.gluten_free { "✅" } else { "❌" },
-        if diet_info.lactose_free { "✅" } else { "❌" },
-        if diet_info.milk_free { "✅" } else { "❌" },
-        if diet_info.low_lactose { "✅" } else { "❌" },
-        if food_info.co2 { "✅" } else { "❌" },
-        if food_info.heart { "✅" } else { "❌" },
-        if food_info.vegan { "✅" } else { "❌" },
-        if food_info.student_recommendation {
-            "✅"
-        } else {
-            "❌"
-        },
-        if food_info.pork { "✅" } else { "❌" },
-        if food_info.fi_meat { "✅" } else { "❌" },
-        if food_info.eu_meat { "✅" } else { "❌" },
-        if food_info.other_meat { "✅" } else { "❌" },
-    ));
-
-    embed
+        t.price,
+        course.price.for_tier(view.tier, view.locale),
+        fmt_diet(&course.diet, view.locale)
+    ))
 }
 
-pub fn fmt_day(day: &str, menu: DailyMenu, extra_string: Option<&str>) -> CreateReply {
-    let meta = menu.meta;
-    let courses = menu.courses;
+/// Whether the dish is listed for the view, the filter and a profile that
+/// hides conflicts leave dishes out
+pub fn is_shown(dish: &Dish, view: &View) -> bool {
+    view.filter.matches(&dish.diet)
+        && (!view.profile.hide_conflicts || view.profile.conflicts(dish).is_empty())
+}
+
+pub fn fmt_day(
+    restaurant: &RestaurantId,
+    day: &str,
+    menu: Menu,
+    extra_string: Option<&str>,
+    view: &View,
+) -> CreateReply {
+    let served = !menu.dishes.is_empty();
+    let courses = menu
+        .dishes
+        .into_iter()
+        .filter(|d| is_shown(d, view))
+        .collect::<Vec<Dish>>();
 
     match courses.len() {
         n if n > 0 => {
@@ -82,31 +159,22 @@ pub fn fmt_day(day: &str, menu: DailyMenu, extra_string: Option<&str>) -> Create
                 r"
     # [{}](<{}>) - {day}
     {}",
-                meta.ref_title,
-                meta.ref_url,
+                menu.title,
+                menu.url,
                 extra_string.unwrap_or_default()
             ));
 
-            for (n, c) in courses {
-                let name = c.title_fi.clone().unwrap_or("N/A".to_string());
-
-                let mut button = CreateButton::new(format!("infoday_{day}_{n}"))
-                    .emoji(ReactionType::Unicode("ℹ️".to_string()));
-
-                match name.len() {
-                    n if n >= 80 => {
-                        let name = &name[0..77];
-                        let name = name.to_string() + "...";
-                        button = button.label(name);
-                    }
-                    _ => {
-                        button = button.label(name);
-                    }
-                }
+            for c in courses {
+                let n = &c.id;
+                let name = c.title(view.locale).to_string();
+
+                let button = CreateButton::new(format!("infoday_{restaurant}_{day}_{n}"))
+                    .emoji(ReactionType::Unicode("ℹ️".to_string()))
+                    .label(truncate(&name, 80));
 
                 buttons.push(button);
 
-                reply = reply.embed(fmt_course(c));
+                reply = reply.embed(fmt_course(&c, view));
             }
 
             // this is a length check for the button vec
@@ -128,33 +196,32 @@ pub fn fmt_day(day: &str, menu: DailyMenu, extra_string: Option<&str>) -> Create
                 }
             }
 
+            let other = view.locale.other();
+            acrs.push(CreateActionRow::Buttons(vec![
+                CreateButton::new(format!(
+                    "translate_{restaurant}_{day}_{}_{}",
+                    other.code(),
+                    view.filter.bits()
+                ))
+                .emoji(ReactionType::Unicode("🌐".to_string()))
+                .label(other.name())
+                .style(ButtonStyle::Secondary),
+            ]));
+
             reply.components(acrs)
         }
         _ => CreateReply::default().content(format!(
             r"
     # [{}](<{}>)
 
-    Ei ruokalistaa päivälle {day}
+    {} {day}
     ",
-            meta.ref_title, meta.ref_url
+            menu.title,
+            menu.url,
+            match served {
+                true => view.locale.strings().no_matching_dishes,
+                false => view.locale.strings().no_menu_for_day,
+            }
         )),
     }
 }
-
-const CENTRIA: u8 = 129;
-
-pub async fn fetch_day(day: &str) -> Result<DailyMenu, Error> {
-    let url = format!("https://sodexo.fi/ruokalistat/output/daily_json/{CENTRIA}/{day}");
-
-    let menu = reqwest::get(url).await?.json::<DailyMenu>().await?;
-
-    Ok(menu)
-}
-
-pub async fn fetch_week() -> Result<WeeklyMenu, Error> {
-    let url = format!("https://sodexo.fi/ruokalistat/output/weekly_json/{CENTRIA}",);
-
-    let menu = reqwest::get(url).await?.json::<WeeklyMenu>().await?;
-
-    Ok(menu)
-}
@@ -0,0 +1,84 @@
+use crate::Data;
+use crate::Error;
+use crate::cache::cached_day;
+use crate::list::fmt_day;
+use crate::locale::Locale;
+use crate::menu::DietFilter;
+use crate::provider::RestaurantId;
+use crate::settings::View;
+use crate::settings::user_view;
+use poise::serenity_prelude as serenity;
+use serenity::all::ComponentInteraction;
+use serenity::all::CreateInteractionResponse;
+use serenity::all::CreateInteractionResponseFollowup;
+use serenity::all::CreateInteractionResponseMessage;
+use serenity::all::EditInteractionResponse;
+use serenity::all::MessageFlags;
+
+/// Shows the day of a [`fmt_day`] message in the other language, only to the
+/// one who pressed the button. A menu that is already only theirs is edited in place.
+pub async fn translate(
+    ctx: &serenity::Context,
+    interaction: &ComponentInteraction,
+    data: &Data,
+) -> Result<(), Error> {
+    let id = &interaction.data.custom_id;
+
+    let in_place = interaction
+        .message
+        .flags
+        .is_some_and(|f| f.contains(MessageFlags::EPHEMERAL));
+
+    let response = match in_place {
+        true => CreateInteractionResponse::Acknowledge,
+        false => CreateInteractionResponse::Defer(
+            CreateInteractionResponseMessage::default().ephemeral(true),
+        ),
+    };
+    interaction.create_response(&ctx.http, response).await?;
+
+    // translate_{restaurant}_{day}_{locale}_{filter}, buttons from before the
+    // filters don't have one
+    let mut info = id.split('_').skip(1);
+    let restaurant: RestaurantId = info.next().ok_or("cannot get restaurant")?.parse()?;
+    let day = info.next().ok_or("cannot get day")?;
+    let locale = info
+        .next()
+        .and_then(Locale::from_discord)
+        .ok_or("cannot get locale")?;
+    let filter = DietFilter::from_bits(info.next().unwrap_or("0").parse()?);
+
+    let guild_id = interaction.guild_id.map(|g| g.get());
+    let order = data.settings.lock().await.course_order(guild_id);
+    let view = View {
+        locale,
+        filter,
+        ..user_view(data, guild_id, interaction.user.id.get(), None).await
+    };
+
+    let mut menu = cached_day(data, &restaurant, day).await?;
+    menu.menu.sort_dishes(order);
+    let note = menu.outdated_note(locale);
+
+    let reply = fmt_day(&restaurant, day, menu.menu, note.as_deref(), &view);
+
+    match in_place {
+        true => {
+            interaction
+                .edit_response(
+                    &ctx.http,
+                    reply.to_slash_initial_response_edit(EditInteractionResponse::new()),
+                )
+                .await?;
+        }
+        false => {
+            let followup = reply
+                .ephemeral(true)
+                .to_slash_followup_response(CreateInteractionResponseFollowup::new());
+
+            interaction.create_followup(&ctx.http, followup).await?;
+        }
+    }
+
+    Ok(())
+}
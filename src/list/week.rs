@@ -0,0 +1,269 @@
+use crate::Data;
+use crate::Error;
+use crate::cache::cached_week;
+use crate::dates::week_start;
+use crate::list::fmt_day;
+use crate::list::is_shown;
+use crate::list::truncate;
+use crate::menu::CourseOrder;
+use crate::menu::DietFilter;
+use crate::menu::Week;
+use crate::provider::RestaurantId;
+use crate::settings::View;
+use crate::settings::user_view;
+use chrono::NaiveDate;
+use poise::CreateReply;
+use poise::serenity_prelude as serenity;
+use serenity::all::ButtonStyle;
+use serenity::all::ComponentInteraction;
+use serenity::all::ComponentInteractionDataKind;
+use serenity::all::CreateActionRow;
+use serenity::all::CreateButton;
+use serenity::all::CreateInteractionResponse;
+use serenity::all::CreateSelectMenu;
+use serenity::all::CreateSelectMenuKind;
+use serenity::all::CreateSelectMenuOption;
+use serenity::all::EditInteractionResponse;
+use serenity::all::ReactionType;
+
+/// Discord allows 5 action rows per message, the navigation takes two of them
+const MAX_COURSE_ROWS: usize = 3;
+
+/// Discord allows 25 options per select menu
+const MAX_SELECT_OPTIONS: usize = 25;
+
+/// The day shown for `date`, the next day with a menu if there is nothing on
+/// `date` and the last day if the week is already over
+fn page(week: &Week, date: NaiveDate) -> usize {
+    week.days
+        .iter()
+        .position(|d| d.date >= date)
+        .unwrap_or(week.days.len().saturating_sub(1))
+}
+
+fn nav_button(
+    restaurant: &RestaurantId,
+    week: &Week,
+    current: usize,
+    target: Option<usize>,
+    nav: &str,
+    view: &View,
+) -> CreateButton {
+    let filter = view.filter.bits();
+    let (date, label) = match target.and_then(|i| week.days.get(i)) {
+        Some(day) => (day.date, day.label(view.locale)),
+        None => (week.days[current].date, "-".to_string()),
+    };
+
+    let label = match nav {
+        "prev" => format!("◀ {label}"),
+        _ => format!("{label} ▶"),
+    };
+
+    CreateButton::new(format!("weekpage_{restaurant}_{date}_{nav}_{filter}"))
+        .label(label)
+        .style(ButtonStyle::Secondary)
+        .disabled(target.is_none())
+}
+
+/// One day of the week with buttons to the previous and next day and a menu
+/// to jump to any day, `week` must have at least one day
+pub fn fmt_week(
+    restaurant: &RestaurantId,
+    mut week: Week,
+    date: NaiveDate,
+    order: CourseOrder,
+    view: &View,
+) -> CreateReply {
+    let current = page(&week, date);
+    let prev = current.checked_sub(1);
+    let next = Some(current + 1).filter(|i| *i < week.days.len());
+
+    let nav = CreateActionRow::Buttons(vec![
+        nav_button(restaurant, &week, current, prev, "prev", view),
+        nav_button(restaurant, &week, current, next, "next", view),
+    ]);
+
+    let options = week
+        .days
+        .iter()
+        .enumerate()
+        .map(|(i, d)| {
+            CreateSelectMenuOption::new(
+                format!("{} {}", d.label(view.locale), d.date.format("%d.%m.")),
+                d.date.to_string(),
+            )
+            .default_selection(i == current)
+        })
+        .collect();
+
+    let select = CreateActionRow::SelectMenu(CreateSelectMenu::new(
+        format!(
+            "weekpage_{restaurant}_{}_select_{}",
+            week.start,
+            view.filter.bits()
+        ),
+        CreateSelectMenuKind::String { options },
+    ));
+
+    let mut day = week.days.remove(current);
+    day.menu.sort_dishes(order);
+    let date = day.date.format("%Y-%m-%d").to_string();
+
+    // the buttons of a long menu don't fit next to the navigation, a select
+    // menu with the same dishes takes a single row
+    let info = CreateActionRow::SelectMenu(
+        CreateSelectMenu::new(
+            format!("infoday_{restaurant}_{date}_select"),
+            CreateSelectMenuKind::String {
+                options: day
+                    .menu
+                    .dishes
+                    .iter()
+                    .filter(|d| is_shown(d, view))
+                    .take(MAX_SELECT_OPTIONS)
+                    .map(|d| {
+                        CreateSelectMenuOption::new(truncate(d.title(view.locale), 100), &d.id)
+                            .emoji(ReactionType::Unicode("ℹ️".to_string()))
+                    })
+                    .collect(),
+            },
+        )
+        .placeholder(view.locale.strings().dish_details),
+    );
+
+    let label = day.label(view.locale);
+    let reply = fmt_day(restaurant, &date, day.menu, Some(&label), view);
+
+    let mut components = reply.components.clone().unwrap_or_default();
+    // the translate button, paging renders the week in the reader's own language anyway
+    components.pop();
+    if components.len() > MAX_COURSE_ROWS {
+        components = vec![info];
+    }
+    components.push(nav);
+    components.push(select);
+
+    reply.components(components)
+}
+
+/// Handles the buttons and the select menu of [`fmt_week`] by editing the message in place
+pub async fn week_page(
+    ctx: &serenity::Context,
+    interaction: &ComponentInteraction,
+    data: &Data,
+) -> Result<(), Error> {
+    let id = &interaction.data.custom_id;
+
+    interaction
+        .create_response(&ctx.http, CreateInteractionResponse::Acknowledge)
+        .await?;
+
+    // weekpage_{restaurant}_{date}_{prev|next|select}_{filter}, buttons from
+    // before the filters don't have one. The buttons already point to their
+    // day, `nav` keeps their ids apart when both are disabled on the same day.
+    let mut info = id.split('_').skip(1);
+    let restaurant: RestaurantId = info.next().ok_or("cannot get restaurant")?.parse()?;
+    let date = info.next().ok_or("cannot get date")?;
+    let nav = info.next().ok_or("cannot get nav")?;
+    let filter = DietFilter::from_bits(info.next().unwrap_or("0").parse()?);
+
+    let date = match (nav, &interaction.data.kind) {
+        ("select", ComponentInteractionDataKind::StringSelect { values }) => {
+            values.first().ok_or("nothing selected")?
+        }
+        ("prev" | "next", ComponentInteractionDataKind::Button) => date,
+        _ => return Err(format!("unknown week page component `{id}`").into()),
+    };
+    let date = NaiveDate::parse_from_str(date, "%Y-%m-%d")?;
+
+    let guild_id = interaction.guild_id.map(|g| g.get());
+    let order = data.settings.lock().await.course_order(guild_id);
+    let view = View {
+        filter,
+        ..user_view(
+            data,
+            guild_id,
+            interaction.user.id.get(),
+            Some(&interaction.locale),
+        )
+        .await
+    };
+
+    let week = cached_week(data, &restaurant, week_start(date)).await?;
+    if week.days.is_empty() {
+        return Err(format!("no menu for the week of {date}").into());
+    }
+
+    let reply = fmt_week(&restaurant, week, date, order, &view);
+
+    interaction
+        .edit_response(
+            &ctx.http,
+            reply.to_slash_initial_response_edit(EditInteractionResponse::new()),
+        )
+        .await?;
+
+    Ok(())
+}
+
+#[test]
+fn week_pages() {
+    use crate::types::week::WeeklyMenu;
+
+    let weekly = std::fs::read_to_string("tests/weekly.json").expect("no file");
+    let menu: WeeklyMenu = serde_json::from_str(&weekly).expect("unable to parse json");
+    let today = NaiveDate::from_ymd_opt(2025, 8, 27).expect("invalid date");
+    let week = crate::provider::sodexo::week(menu, today).expect("unable to convert week");
+
+    let d = |day| NaiveDate::from_ymd_opt(2025, 8, day).expect("invalid date");
+    let last = week.days.len() - 1;
+
+    assert_eq!(page(&week, d(25)), 0);
+    assert_eq!(page(&week, d(27)), 2);
+    assert_eq!(page(&week, d(20)), 0);
+    assert_eq!(
+        page(&week, week.days[last].date + chrono::Days::new(1)),
+        last
+    );
+}
+
+#[test]
+fn long_menus_fit() {
+    use crate::types::week::WeeklyMenu;
+
+    let weekly = std::fs::read_to_string("tests/weekly.json").expect("no file");
+    let menu: WeeklyMenu = serde_json::from_str(&weekly).expect("unable to parse json");
+    let today = NaiveDate::from_ymd_opt(2025, 8, 27).expect("invalid date");
+    let mut week = crate::provider::sodexo::week(menu, today).expect("unable to convert week");
+    let view = View::default();
+
+    let rows = |week: &Week| {
+        fmt_week(
+            &RestaurantId::default(),
+            week.clone(),
+            week.start,
+            CourseOrder::default(),
+            &view,
+        )
+        .components
+        .unwrap_or_default()
+    };
+
+    // a short menu keeps its buttons
+    assert!(week.days[0].menu.dishes.len() <= 5);
+    let components = rows(&week);
+    assert_eq!(components.len(), 3);
+    assert!(matches!(components[0], CreateActionRow::Buttons(_)));
+
+    // more than 15 dishes are listed in a single select menu instead
+    let first = week.days[0].menu.dishes[0].clone();
+    for i in 0..20 {
+        let mut dish = first.clone();
+        dish.id = format!("extra{i}");
+        week.days[0].menu.dishes.push(dish);
+    }
+    let components = rows(&week);
+    assert_eq!(components.len(), 3);
+    assert!(matches!(components[0], CreateActionRow::SelectMenu(_)));
+}
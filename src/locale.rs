@@ -0,0 +1,324 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, poise::ChoiceParameter,
+)]
+pub enum Locale {
+    #[default]
+    #[name = "Suomi"]
+    Fi,
+    #[name = "English"]
+    En,
+}
+
+impl Locale {
+    /// Discord's locale of the user, like `fi` or `en-US`, `None` for the ones
+    /// there is no translation for
+    pub fn from_discord(locale: &str) -> Option<Self> {
+        match locale.split('-').next() {
+            Some("fi") => Some(Locale::Fi),
+            Some("en") => Some(Locale::En),
+            _ => None,
+        }
+    }
+
+    /// Short code used in button ids, [`Locale::from_discord`] reads it back
+    pub fn code(self) -> &'static str {
+        match self {
+            Locale::Fi => "fi",
+            Locale::En => "en",
+        }
+    }
+
+    /// The language the translate button switches to
+    pub fn other(self) -> Self {
+        match self {
+            Locale::Fi => Locale::En,
+            Locale::En => Locale::Fi,
+        }
+    }
+
+    pub fn strings(self) -> &'static Strings {
+        match self {
+            Locale::Fi => &FI,
+            Locale::En => &EN,
+        }
+    }
+}
+
+/// Everything shown around the menus, the menus themselves come in the
+/// caterer's language
+pub struct Strings {
+    pub gluten_free: &'static str,
+    pub lactose_free: &'static str,
+    pub milk_free: &'static str,
+    pub low_lactose: &'static str,
+    pub low_co2: &'static str,
+    pub better_choice: &'static str,
+    pub vegan: &'static str,
+    pub student_recommendation: &'static str,
+    pub pork: &'static str,
+    pub fi_meat: &'static str,
+    pub eu_meat: &'static str,
+    pub other_meat: &'static str,
+
+    pub no_pork: &'static str,
+    pub fi_meat_only: &'static str,
+    pub not_for_you: &'static str,
+    pub dish_details: &'static str,
+    pub avoided_allergens: &'static str,
+    pub avoided_ingredients: &'static str,
+    pub group: &'static str,
+    pub suits_everyone: &'static str,
+    pub suits_not_everyone: &'static str,
+    pub suits_no_one: &'static str,
+    pub no_shared_profile: &'static str,
+    pub followed_on_menu: &'static str,
+
+    pub price: &'static str,
+    pub diet_codes: &'static str,
+    pub unknown_diet_code: &'static str,
+    pub no_menu_for_day: &'static str,
+    pub no_menu_for_week: &'static str,
+    pub no_matching_dishes: &'static str,
+    pub outdated: &'static str,
+
+    pub recipes: &'static str,
+    pub ingredients: &'static str,
+    pub nutrition: &'static str,
+    pub nutrition_total: &'static str,
+    pub allergens: &'static str,
+    pub other_allergens: &'static str,
+
+    pub schedule_created: &'static str,
+    pub no_schedules: &'static str,
+    pub schedule_deleted: &'static str,
+    pub fetch_failed: &'static str,
+
+    pub current_restaurant: &'static str,
+    pub channel_restaurant_set: &'static str,
+    pub channel_restaurant_removed: &'static str,
+    pub guild_restaurant_set: &'static str,
+    pub guild_settings_only: &'static str,
+    pub course_order_set: &'static str,
+    pub locale_set: &'static str,
+    pub current_price_tier: &'static str,
+    pub no_price_tier: &'static str,
+    pub price_tier_set: &'static str,
+    pub guild_price_tier_set: &'static str,
+    pub price_tier_needs_permission: &'static str,
+    pub refresh_failed: &'static str,
+    pub restaurants_discovered: &'static str,
+    pub refresh_needs_permission: &'static str,
+    pub no_restaurants: &'static str,
+
+    pub profile_empty: &'static str,
+    pub diet: &'static str,
+    pub profile_allergens: &'static str,
+    pub profile_ingredients: &'static str,
+    pub conflicting_dishes: &'static str,
+    pub conflicts_hidden: &'static str,
+    pub conflicts_marked: &'static str,
+    pub group_checks: &'static str,
+    pub shared_in_groups: &'static str,
+    pub not_shared_in_groups: &'static str,
+    pub unknown_allergen: &'static str,
+    pub avoid_as_ingredient: &'static str,
+    pub missing_ingredient: &'static str,
+
+    pub no_follows: &'static str,
+    pub follows_intro: &'static str,
+    pub followed_dish: &'static str,
+    pub followed_ingredient: &'static str,
+    pub in_restaurant: &'static str,
+    pub missing_query: &'static str,
+    pub unknown_follow: &'static str,
+}
+
+const FI: Strings = Strings {
+    gluten_free: "Gluteeniton",
+    lactose_free: "Laktoositon",
+    milk_free: "Maidoton",
+    low_lactose: "Vähälaktoosinen",
+    low_co2: "=< 0.5 kg CO2 Päästöt",
+    better_choice: "Parempi Valinta",
+    vegan: "Vegaaninen",
+    student_recommendation: "Opiskelijaruokailusuositusten mukainen",
+    pork: "Sisältää porsaanlihaa",
+    fi_meat: "Liha Suomesta",
+    eu_meat: "Liha muualta EU:sta",
+    other_meat: "Liha muualta",
+
+    no_pork: "Ei porsasta",
+    fi_meat_only: "Vain suomalaista lihaa",
+    not_for_you: "Ei sovi profiiliisi",
+    dish_details: "Ruoan lisätiedot",
+    avoided_allergens: "Sisältää välttämiäsi allergeeneja",
+    avoided_ingredients: "Sisältää välttämiäsi ainesosia",
+    group: "Ryhmä",
+    suits_everyone: "Sopii kaikille",
+    suits_not_everyone: "Ei sovi kaikille",
+    suits_no_one: "Mikään ruoka ei sovi kaikille",
+    no_shared_profile: "Ei ryhmille jaettua profiilia, ei huomioitu",
+    followed_on_menu: "Seuraamasi ruoka on tulossa ruokalistalle",
+
+    price: "Hinta",
+    diet_codes: "Merkinnät",
+    unknown_diet_code: "Tuntematon merkintä",
+    no_menu_for_day: "Ei ruokalistaa päivälle",
+    no_menu_for_week: "Ei ruokalistaa viikolle",
+    no_matching_dishes: "Ei suodattimia vastaavia ruokia päivälle",
+    outdated: "⚠️ Ruokalista voi olla vanhentunut, haettu",
+
+    recipes: "Reseptit",
+    ingredients: "Ainesosat",
+    nutrition: "Ravintosisältö",
+    nutrition_total: "Ravintosisältö yhteensä",
+    allergens: "Allergeenit",
+    other_allergens: "Muut",
+
+    schedule_created: "Ajoitettu ruokalista luotu ajastuksella",
+    no_schedules: "Ei ajastettuja ruokalistoja",
+    schedule_deleted: "Poistettu ajastettu ruokalista",
+    fetch_failed: "Ruokalistan haku epäonnistui",
+
+    current_restaurant: "Nykyinen ravintola on",
+    channel_restaurant_set: "Kanavan ravintolaksi asetettu",
+    channel_restaurant_removed: "Kanavan oma ravintola poistettu",
+    guild_restaurant_set: "Palvelimen ravintolaksi asetettu",
+    guild_settings_only: "palvelimen asetuksia voi muuttaa vain palvelimella",
+    course_order_set: "Ruokien järjestykseksi asetettu",
+    locale_set: "Palvelimen kieleksi asetettu",
+    current_price_tier: "Nykyinen hintaluokka on",
+    no_price_tier: "Hintaluokkaa ei ole valittu, kaikki hinnat näytetään",
+    price_tier_set: "Hintaluokaksi asetettu",
+    guild_price_tier_set: "Palvelimen hintaluokaksi asetettu",
+    price_tier_needs_permission: "palvelimen hintaluokan asettaminen vaatii viestien hallintaoikeuden",
+    refresh_failed: "Päivitys epäonnistui ravintoloille",
+    restaurants_discovered: "Uusia ravintoloita löytyi",
+    refresh_needs_permission: "ravintoloiden päivittäminen vaatii viestien hallintaoikeuden palvelimella",
+    no_restaurants: "Ei ravintoloita",
+
+    profile_empty: "Profiilisi on tyhjä, kaikki ruoat näytetään",
+    diet: "Ruokavalio",
+    profile_allergens: "Vältettävät allergeenit",
+    profile_ingredients: "Vältettävät ainesosat",
+    conflicting_dishes: "Sopimattomat ruoat",
+    conflicts_hidden: "piilotetaan",
+    conflicts_marked: "merkitään",
+    group_checks: "Ryhmät",
+    shared_in_groups: "profiili jaetaan komennolle `/ryhma`",
+    not_shared_in_groups: "profiilia ei jaeta",
+    unknown_allergen: "tuntematon allergeeni",
+    avoid_as_ingredient: "muita voi välttää komennolla `/profiili ainesosa`",
+    missing_ingredient: "ainesosa puuttuu",
+
+    no_follows: "Et seuraa mitään",
+    follows_intro: "Saat yksityisviestin, kun seuraamasi ruoka on tulossa ruokalistalle",
+    followed_dish: "ruoka",
+    followed_ingredient: "ainesosa",
+    in_restaurant: "ravintolassa",
+    missing_query: "hakusana puuttuu",
+    unknown_follow: "tuntematon seuranta",
+};
+
+const EN: Strings = Strings {
+    gluten_free: "Gluten free",
+    lactose_free: "Lactose free",
+    milk_free: "Milk free",
+    low_lactose: "Low lactose",
+    low_co2: "=< 0.5 kg CO2 emissions",
+    better_choice: "Better choice",
+    vegan: "Vegan",
+    student_recommendation: "Follows the student meal recommendations",
+    pork: "Contains pork",
+    fi_meat: "Meat from Finland",
+    eu_meat: "Meat from elsewhere in the EU",
+    other_meat: "Meat from outside the EU",
+
+    no_pork: "No pork",
+    fi_meat_only: "Only Finnish meat",
+    not_for_you: "Conflicts with your profile",
+    dish_details: "Dish details",
+    avoided_allergens: "Contains allergens you avoid",
+    avoided_ingredients: "Contains ingredients you avoid",
+    group: "Group",
+    suits_everyone: "Suits everyone",
+    suits_not_everyone: "Doesn't suit everyone",
+    suits_no_one: "No dish suits everyone",
+    no_shared_profile: "No profile shared with groups, not considered",
+    followed_on_menu: "A dish you follow is coming up on the menu",
+
+    price: "Price",
+    diet_codes: "Diet codes",
+    unknown_diet_code: "Unknown code",
+    no_menu_for_day: "No menu for",
+    no_menu_for_week: "No menu for the week",
+    no_matching_dishes: "No dishes matching the filters for",
+    outdated: "⚠️ The menu may be out of date, fetched",
+
+    recipes: "Recipes",
+    ingredients: "Ingredients",
+    nutrition: "Nutrition",
+    nutrition_total: "Total nutrition",
+    allergens: "Allergens",
+    other_allergens: "Other",
+
+    schedule_created: "Scheduled menu created with",
+    no_schedules: "No scheduled menus",
+    schedule_deleted: "Deleted scheduled menu",
+    fetch_failed: "Error fetching menu",
+
+    current_restaurant: "The current restaurant is",
+    channel_restaurant_set: "The channel's restaurant set to",
+    channel_restaurant_removed: "The channel's own restaurant removed",
+    guild_restaurant_set: "The server's restaurant set to",
+    guild_settings_only: "server settings can only be changed on a server",
+    course_order_set: "Dish order set to",
+    locale_set: "The server's language set to",
+    current_price_tier: "The current price tier is",
+    no_price_tier: "No price tier chosen, all prices are shown",
+    price_tier_set: "Price tier set to",
+    guild_price_tier_set: "The server's price tier set to",
+    price_tier_needs_permission: "setting the server's price tier requires the manage messages permission",
+    refresh_failed: "Refreshing failed for the restaurants",
+    restaurants_discovered: "New restaurants found",
+    refresh_needs_permission: "refreshing the restaurants requires the manage messages permission on a server",
+    no_restaurants: "No restaurants",
+
+    profile_empty: "Your profile is empty, all dishes are shown",
+    diet: "Diet",
+    profile_allergens: "Allergens you avoid",
+    profile_ingredients: "Ingredients you avoid",
+    conflicting_dishes: "Dishes that don't suit you",
+    conflicts_hidden: "hidden",
+    conflicts_marked: "marked",
+    group_checks: "Groups",
+    shared_in_groups: "the profile is shared with `/group`",
+    not_shared_in_groups: "the profile isn't shared",
+    unknown_allergen: "unknown allergen",
+    avoid_as_ingredient: "others can be avoided with `/profile ingredient`",
+    missing_ingredient: "the ingredient is missing",
+
+    no_follows: "You don't follow anything",
+    follows_intro: "You get a DM when a dish you follow is coming up on the menu",
+    followed_dish: "dish",
+    followed_ingredient: "ingredient",
+    in_restaurant: "in restaurant",
+    missing_query: "the search term is missing",
+    unknown_follow: "unknown follow",
+};
+
+#[test]
+fn discord_locales() {
+    assert_eq!(Locale::from_discord("fi"), Some(Locale::Fi));
+    assert_eq!(Locale::from_discord("en-US"), Some(Locale::En));
+    assert_eq!(Locale::from_discord("en-GB"), Some(Locale::En));
+    assert_eq!(Locale::from_discord("sv-SE"), None);
+
+    for locale in [Locale::Fi, Locale::En] {
+        assert_eq!(Locale::from_discord(locale.code()), Some(locale));
+        assert_eq!(locale.other().other(), locale);
+    }
+}
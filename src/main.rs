@@ -1,8 +1,14 @@
+use crate::cache::MenuCache;
 use crate::error::on_error;
 use crate::event::event_handler;
+use crate::follow::Follows;
+use crate::follow::create_follow_job;
+use crate::restaurants::Directory;
+use crate::profile::Profiles;
 use crate::schedule::DataJob;
 use crate::schedule::StoredJob;
 use crate::schedule::create_scheduled_day_post;
+use crate::settings::Settings;
 use ::serenity::all::ChannelId;
 use poise::serenity_prelude::ClientBuilder;
 use poise::serenity_prelude::GatewayIntents;
@@ -14,16 +20,31 @@ use ::serenity::all::CreateInteractionResponseFollowup;
 use std::env;
 use tokio_cron_scheduler::JobScheduler;
 
+pub(crate) mod cache;
 pub(crate) mod commands;
+pub(crate) mod dates;
 pub(crate) mod error;
 pub(crate) mod list;
+pub(crate) mod locale;
+pub(crate) mod menu;
+pub(crate) mod profile;
+pub(crate) mod provider;
+pub(crate) mod restaurants;
 pub(crate) mod schedule;
+pub(crate) mod settings;
 pub(crate) mod types;
 pub(crate) mod event;
+pub(crate) mod follow;
 
+#[derive(Clone)]
 pub struct Data {
     sched: Arc<Mutex<JobScheduler>>,
     job_uuids: Arc<Mutex<Vec<DataJob>>>,
+    settings: Arc<Mutex<Settings>>,
+    profiles: Arc<Mutex<Profiles>>,
+    follows: Arc<Mutex<Follows>>,
+    restaurants: Arc<Mutex<Directory>>,
+    cache: Arc<Mutex<MenuCache>>,
 }
 
 pub type Error = Box<dyn std::error::Error + Send + Sync>;
@@ -44,6 +65,14 @@ async fn main() -> Result<(), Error> {
             commands::schedule::schedule_day(),
             commands::schedule::list_scheduled(),
             commands::schedule::delete_scheduled(),
+            commands::settings::set_restaurant(),
+            commands::settings::set_course_order(),
+            commands::settings::set_price_tier(),
+            commands::settings::set_locale(),
+            commands::restaurants::search_restaurants(),
+            commands::profile::profile(),
+            commands::group::group_menu(),
+            commands::follow::follow(),
         ],
         on_error: |error| Box::pin(on_error(error)),
         event_handler: |ctx, event, framework, data| {
@@ -64,29 +93,42 @@ async fn main() -> Result<(), Error> {
 
                 ctx.online();
 
-                let mut uuids: Vec<DataJob> = Vec::new();
+                let data = Data {
+                    sched: Arc::new(Mutex::new(scheduler)),
+                    job_uuids: Arc::new(Mutex::new(Vec::new())),
+                    settings: Arc::new(Mutex::new(Settings::load()?)),
+                    profiles: Arc::new(Mutex::new(Profiles::load()?)),
+                    follows: Arc::new(Mutex::new(Follows::load()?)),
+                    restaurants: Arc::new(Mutex::new(Directory::load()?)),
+                    cache: Arc::new(Mutex::new(MenuCache::from_env()?)),
+                };
 
                 let jobs: Vec<StoredJob> =
                     serde_json::from_str(&read_to_string("jobs.json").unwrap_or("[]".to_string()))?;
 
                 for i in jobs {
-                    if let Ok(job) =
-                        create_scheduled_day_post(ctx, &i.cron, ChannelId::new(i.channel_id))
-                    {
-                        uuids.push(DataJob {
+                    if let Ok(job) = create_scheduled_day_post(
+                        ctx,
+                        data.clone(),
+                        &i.cron,
+                        ChannelId::new(i.channel_id),
+                        i.guild_id,
+                    ) {
+                        data.job_uuids.lock().await.push(DataJob {
                             uuid: job.guid(),
                             cron: i.cron,
                             channel_id: i.channel_id,
+                            guild_id: i.guild_id,
                         });
 
-                        scheduler.add(job).await?;
+                        data.sched.lock().await.add(job).await?;
                     }
                 }
 
-                Ok(Data {
-                    sched: Arc::new(Mutex::new(scheduler)),
-                    job_uuids: Arc::new(Mutex::new(uuids)),
-                })
+                let follow_job = create_follow_job(ctx, data.clone())?;
+                data.sched.lock().await.add(follow_job).await?;
+
+                Ok(data)
             })
         })
         .options(options)
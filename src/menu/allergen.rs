@@ -0,0 +1,339 @@
+use crate::locale::Locale;
+use serde::Deserialize;
+use serde::Serialize;
+use std::fmt::Display;
+
+/// The 14 allergens the EU requires to be declared, followed by ones
+/// caterers commonly list on top of them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum AllergenKind {
+    Gluten,
+    Crustaceans,
+    Eggs,
+    Fish,
+    Peanuts,
+    Soy,
+    Milk,
+    Nuts,
+    Celery,
+    Mustard,
+    Sesame,
+    Sulphites,
+    Lupin,
+    Molluscs,
+
+    Chili,
+    Coriander,
+    Onion,
+    Garlic,
+
+    /// Listed by the caterer but not recognized
+    Unknown,
+}
+
+/// Every kind but [`AllergenKind::Unknown`] with its finnish and english name
+pub const KINDS: &[(AllergenKind, &str, &str)] = &[
+    (AllergenKind::Gluten, "Gluteeni", "Gluten"),
+    (AllergenKind::Crustaceans, "Äyriäiset", "Crustaceans"),
+    (AllergenKind::Eggs, "Kananmuna", "Eggs"),
+    (AllergenKind::Fish, "Kala", "Fish"),
+    (AllergenKind::Peanuts, "Maapähkinä", "Peanuts"),
+    (AllergenKind::Soy, "Soija", "Soy"),
+    (AllergenKind::Milk, "Maito", "Milk"),
+    (AllergenKind::Nuts, "Pähkinät", "Nuts"),
+    (AllergenKind::Celery, "Selleri", "Celery"),
+    (AllergenKind::Mustard, "Sinappi", "Mustard"),
+    (AllergenKind::Sesame, "Seesami", "Sesame"),
+    (AllergenKind::Sulphites, "Sulfiitit", "Sulphites"),
+    (AllergenKind::Lupin, "Lupiini", "Lupin"),
+    (AllergenKind::Molluscs, "Nilviäiset", "Molluscs"),
+    (AllergenKind::Chili, "Chili", "Chili"),
+    (AllergenKind::Coriander, "Korianteri", "Coriander"),
+    (AllergenKind::Onion, "Sipuli", "Onion"),
+    (AllergenKind::Garlic, "Valkosipuli", "Garlic"),
+];
+
+/// Lowercase stems of the names caterers use, stems that change when inflected
+/// are listed in each form. A word is matched with [`is_form`], so `munakoiso`
+/// isn't an egg and `gluteeniton` isn't gluten.
+const NAMES: &[(&str, AllergenKind)] = &[
+    ("gluteeni", AllergenKind::Gluten),
+    ("vehnä", AllergenKind::Gluten),
+    ("ohra", AllergenKind::Gluten),
+    ("ruis", AllergenKind::Gluten),
+    ("rukii", AllergenKind::Gluten),
+    ("kaura", AllergenKind::Gluten),
+    ("speltti", AllergenKind::Gluten),
+    ("kamut", AllergenKind::Gluten),
+    ("äyriäinen", AllergenKind::Crustaceans),
+    ("äyriäise", AllergenKind::Crustaceans),
+    ("äyriäisi", AllergenKind::Crustaceans),
+    ("äyriäis", AllergenKind::Crustaceans),
+    ("muna", AllergenKind::Eggs),
+    ("kananmuna", AllergenKind::Eggs),
+    ("kala", AllergenKind::Fish),
+    ("maapähkinä", AllergenKind::Peanuts),
+    ("soija", AllergenKind::Soy),
+    ("maito", AllergenKind::Milk),
+    ("maido", AllergenKind::Milk),
+    ("juusto", AllergenKind::Milk),
+    ("kerma", AllergenKind::Milk),
+    ("laktoosi", AllergenKind::Milk),
+    ("pähkinä", AllergenKind::Nuts),
+    ("manteli", AllergenKind::Nuts),
+    ("selleri", AllergenKind::Celery),
+    ("sinappi", AllergenKind::Mustard),
+    ("sinapi", AllergenKind::Mustard),
+    ("seesami", AllergenKind::Sesame),
+    ("rikkidioksidi", AllergenKind::Sulphites),
+    ("sulfiitti", AllergenKind::Sulphites),
+    ("sulfiiti", AllergenKind::Sulphites),
+    ("sulfiitte", AllergenKind::Sulphites),
+    ("lupiini", AllergenKind::Lupin),
+    ("nilviäinen", AllergenKind::Molluscs),
+    ("nilviäise", AllergenKind::Molluscs),
+    ("nilviäisi", AllergenKind::Molluscs),
+    ("nilviäis", AllergenKind::Molluscs),
+    ("chili", AllergenKind::Chili),
+    ("korianteri", AllergenKind::Coriander),
+    ("valkosipuli", AllergenKind::Garlic),
+    ("sipuli", AllergenKind::Onion),
+];
+
+/// Case endings of the stems, like the `n` in `maidon` or the `t` in `munat`
+const ENDINGS: &[&str] = &[
+    "", "n", "a", "ä", "t", "ta", "tä", "ja", "jä", "jen", "ten", "sta", "stä", "ssa", "ssä",
+    "lla", "llä",
+];
+
+/// Words that make a compound of an allergen still mean the allergen, like
+/// `soijapavut` or `sinapinsiemen`
+const COMPOUNDS: &[&str] = &[
+    "siemen",
+    "siemenet",
+    "papu",
+    "pavut",
+    "jauho",
+    "jauhot",
+    "jauhe",
+    "lese",
+    "hiutale",
+    "hiutaleet",
+    "gluteeni",
+    "proteiini",
+    "valmiste",
+    "tuotteet",
+    "lesitiini",
+    "kastike",
+    "keltuainen",
+    "valkuainen",
+    "öljy",
+];
+
+/// Whether `word` is `stem` with a case ending, optionally followed by a word
+/// from [`COMPOUNDS`]
+fn is_form(word: &str, stem: &str) -> bool {
+    word.strip_prefix(stem).is_some_and(|rest| {
+        ENDINGS.iter().any(|ending| {
+            rest.strip_prefix(ending)
+                .is_some_and(|tail| tail.is_empty() || COMPOUNDS.contains(&tail))
+        })
+    })
+}
+
+impl AllergenKind {
+    pub fn parse(name: &str) -> Self {
+        let name = name.trim().to_lowercase();
+        let words = name
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .collect::<Vec<&str>>();
+
+        words
+            .iter()
+            .find_map(|word| NAMES.iter().find(|(stem, _)| is_form(word, stem)))
+            .map(|(_, kind)| *kind)
+            // hasselpähkinä, cashewpähkinä, ...
+            .or(name.contains("pähkinä").then_some(AllergenKind::Nuts))
+            .unwrap_or(AllergenKind::Unknown)
+    }
+
+    /// Name of the kind itself, caterers name them more specifically like `Vehnä`
+    pub fn name(self, locale: Locale) -> Option<&'static str> {
+        KINDS
+            .iter()
+            .find(|(kind, ..)| *kind == self)
+            .map(|(_, fi, en)| match locale {
+                Locale::Fi => *fi,
+                Locale::En => *en,
+            })
+    }
+
+    /// A kind by its name in either language, or anything [`AllergenKind::parse`] knows
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim();
+
+        KINDS
+            .iter()
+            .find(|(_, fi, en)| fi.eq_ignore_ascii_case(name) || en.eq_ignore_ascii_case(name))
+            .map(|(kind, ..)| *kind)
+            .or(Some(AllergenKind::parse(name)))
+            .filter(|kind| *kind != AllergenKind::Unknown)
+    }
+
+    pub fn is_eu14(self) -> bool {
+        self <= AllergenKind::Molluscs
+    }
+}
+
+/// An allergen as the caterer named it, `Vehnä` and `Kaura` are both [`AllergenKind::Gluten`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Allergen {
+    pub kind: AllergenKind,
+    pub name: String,
+}
+
+impl Allergen {
+    pub fn parse(name: &str) -> Self {
+        Allergen {
+            kind: AllergenKind::parse(name),
+            name: name.trim().to_string(),
+        }
+    }
+
+    /// `Vehnä` is `Vehnä (Gluten)` in english, unknown ones are kept in finnish
+    pub fn localized(&self, locale: Locale) -> String {
+        match (locale, self.kind.name(Locale::En)) {
+            (Locale::En, Some(en)) if !en.eq_ignore_ascii_case(&self.name) => {
+                format!("{} ({en})", self.name)
+            }
+            _ => self.name.clone(),
+        }
+    }
+}
+
+impl Display for Allergen {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+/// A dish's allergens in the order the caterer listed them, without duplicates
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Allergens(pub Vec<Allergen>);
+
+impl Allergens {
+    /// Parses a comma separated list like `Chili, Herneet, Rikkidioksidi ja sulfiitit`
+    pub fn parse(s: Option<&str>) -> Self {
+        let mut allergens: Vec<Allergen> = Vec::new();
+
+        for a in s.unwrap_or_default().split(',').map(str::trim) {
+            if !a.is_empty()
+                && !allergens
+                    .iter()
+                    .any(|b| b.name.to_lowercase() == a.to_lowercase())
+            {
+                allergens.push(Allergen::parse(a));
+            }
+        }
+
+        Allergens(allergens)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Allergen> {
+        self.0.iter()
+    }
+}
+
+impl Display for Allergens {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names = self.iter().map(|a| a.name.as_str()).collect::<Vec<&str>>();
+
+        f.write_str(&names.join(", "))
+    }
+}
+
+#[test]
+fn allergen_list() {
+    let allergens = Allergens::parse(Some(
+        "Chili, Herneet, Rikkidioksidi ja sulfiitit, Valkosipuli, Sipuli, chili, ",
+    ));
+
+    let kinds = allergens
+        .iter()
+        .map(|a| a.kind)
+        .collect::<Vec<AllergenKind>>();
+    assert_eq!(
+        kinds,
+        vec![
+            AllergenKind::Chili,
+            AllergenKind::Unknown,
+            AllergenKind::Sulphites,
+            AllergenKind::Garlic,
+            AllergenKind::Onion,
+        ]
+    );
+    assert_eq!(
+        allergens.to_string(),
+        "Chili, Herneet, Rikkidioksidi ja sulfiitit, Valkosipuli, Sipuli"
+    );
+
+    assert!(allergens.iter().any(|a| a.kind == AllergenKind::Sulphites));
+    assert!(!allergens.iter().any(|a| a.kind == AllergenKind::Milk));
+    assert!(Allergens::parse(None).0.is_empty());
+}
+
+#[test]
+fn allergen_kinds() {
+    let kind = AllergenKind::parse;
+
+    assert_eq!(
+        kind("Gluteenia sisältävät viljat ja -tuotteet"),
+        AllergenKind::Gluten
+    );
+    assert_eq!(kind("Vehnä"), AllergenKind::Gluten);
+    assert_eq!(kind("Kaura"), AllergenKind::Gluten);
+    assert_eq!(kind("Maapähkinät"), AllergenKind::Peanuts);
+    assert_eq!(kind("Hasselpähkinä"), AllergenKind::Nuts);
+    assert_eq!(kind("Munat"), AllergenKind::Eggs);
+    assert_eq!(kind("Seesaminsiemenet"), AllergenKind::Sesame);
+    assert_eq!(kind("Soijapavut"), AllergenKind::Soy);
+    assert_eq!(kind("Korianteri"), AllergenKind::Coriander);
+    assert_eq!(kind("JUUSTO"), AllergenKind::Milk);
+    assert_eq!(kind("Hiivauute"), AllergenKind::Unknown);
+
+    // only whole words and their compounds with another allergen word
+    assert_eq!(kind("Munakoiso"), AllergenKind::Unknown);
+    assert_eq!(kind("maitohappo"), AllergenKind::Unknown);
+    assert_eq!(kind("LAKTOOSITON"), AllergenKind::Unknown);
+    assert_eq!(kind("GLUTEENITON"), AllergenKind::Unknown);
+    assert_eq!(kind("Maidon"), AllergenKind::Milk);
+    assert_eq!(kind("Rukiista"), AllergenKind::Gluten);
+    assert_eq!(kind("Äyriäiset"), AllergenKind::Crustaceans);
+    assert_eq!(kind("VEHNÄJAUHO"), AllergenKind::Gluten);
+    assert_eq!(kind("SINAPINSIEMEN"), AllergenKind::Mustard);
+    assert_eq!(kind("KANANMUNANKELTUAINEN"), AllergenKind::Eggs);
+
+    assert_eq!(
+        Allergen::parse("Vehnä").localized(Locale::En),
+        "Vehnä (Gluten)"
+    );
+    assert_eq!(Allergen::parse("Vehnä").localized(Locale::Fi), "Vehnä");
+    assert_eq!(Allergen::parse("Chili").localized(Locale::En), "Chili");
+    assert_eq!(
+        Allergen::parse("Hiivauute").localized(Locale::En),
+        "Hiivauute"
+    );
+
+    // every kind can be found by its own name
+    for (kind, fi, en) in KINDS {
+        assert_eq!(AllergenKind::parse(fi), *kind);
+        assert_eq!(AllergenKind::from_name(en), Some(*kind));
+    }
+    assert_eq!(AllergenKind::from_name("vehnä"), Some(AllergenKind::Gluten));
+    assert_eq!(AllergenKind::from_name("Hiivauute"), None);
+
+    assert!(AllergenKind::Molluscs.is_eu14());
+    assert!(!AllergenKind::Chili.is_eu14());
+    assert!(!AllergenKind::Unknown.is_eu14());
+}
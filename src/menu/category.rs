@@ -0,0 +1,82 @@
+use crate::locale::Locale;
+use poise::ChoiceParameter;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Declared in the order the courses are listed when sorting by category
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Category {
+    Main,
+    Vegan,
+    Soup,
+    Bakery,
+    Dessert,
+    #[default]
+    Other,
+}
+
+impl Category {
+    /// Sodexo uses english names like `FROM THE FIELD-VEGAN`, Compass finnish ones like `Kasvislounas`
+    pub fn parse(s: Option<&str>) -> Self {
+        let s = s.unwrap_or_default().to_lowercase();
+
+        if s.contains("vegan") || s.contains("kasvis") {
+            Category::Vegan
+        } else if s.contains("soup") || s.contains("keitto") {
+            Category::Soup
+        } else if s.contains("bakery") || s.contains("leipomo") {
+            Category::Bakery
+        } else if s.contains("sweet") || s.contains("jälki") {
+            Category::Dessert
+        } else if s.contains("favorites") || s.contains("grill") || s.contains("lounas") {
+            Category::Main
+        } else {
+            Category::Other
+        }
+    }
+}
+
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, poise::ChoiceParameter,
+)]
+pub enum CourseOrder {
+    /// The order the restaurant lists the courses in
+    #[default]
+    #[name = "Ravintolan järjestys"]
+    #[name_localized("en-US", "Restaurant's order")]
+    #[name_localized("en-GB", "Restaurant's order")]
+    Restaurant,
+    #[name = "Kategorioittain"]
+    #[name_localized("en-US", "By category")]
+    #[name_localized("en-GB", "By category")]
+    Category,
+}
+
+impl CourseOrder {
+    pub fn label(self, locale: Locale) -> &'static str {
+        match locale {
+            Locale::Fi => self.name(),
+            Locale::En => self.localized_name("en-US").unwrap_or(self.name()),
+        }
+    }
+}
+
+#[test]
+fn categories() {
+    assert_eq!(
+        Category::parse(Some("FROM THE FIELD-VEGAN")),
+        Category::Vegan
+    );
+    assert_eq!(
+        Category::parse(Some("From our favorites 1")),
+        Category::Main
+    );
+    assert_eq!(Category::parse(Some("FROM THE GRILL")), Category::Main);
+    assert_eq!(Category::parse(Some("FROM THE SOUP BOWL")), Category::Soup);
+    assert_eq!(Category::parse(Some("From our bakery")), Category::Bakery);
+    assert_eq!(Category::parse(Some("FROM THE SWEET")), Category::Dessert);
+    assert_eq!(Category::parse(Some("Kasvislounas")), Category::Vegan);
+    assert_eq!(Category::parse(Some("Keittolounas")), Category::Soup);
+    assert_eq!(Category::parse(Some("Jälkiruoka")), Category::Dessert);
+    assert_eq!(Category::parse(None), Category::Other);
+}
@@ -0,0 +1,492 @@
+use crate::locale::Locale;
+use crate::types::common::DietInfo;
+use crate::types::common::FoodInfo;
+use serde::Deserialize;
+use serde::Serialize;
+use std::fmt::Display;
+
+/// The letter codes caterers put after a dish's name, like the `G, M` in
+/// `Kanakastike G, M`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DietCode {
+    GlutenFree,
+    LactoseFree,
+    LowLactose,
+    MilkFree,
+    Vegan,
+    Vegetarian,
+    /// `*`, follows the finnish nutrition recommendations. Not the same as the
+    /// heart icon of [`DietFlags::better_choice`], so it's only shown as a code.
+    Recommended,
+    ClimateFriendly,
+    FreshGarlic,
+    /// `A`, ask the staff about allergens
+    Allergens,
+    /// Kept as sent, so a new code is still shown
+    Unknown(String),
+}
+
+/// The code caterers write and its meaning in finnish and english
+type Code = (&'static str, DietCode, &'static str, &'static str);
+
+const CODES: &[Code] = &[
+    ("G", DietCode::GlutenFree, "Gluteeniton", "Gluten free"),
+    ("L", DietCode::LactoseFree, "Laktoositon", "Lactose free"),
+    ("VL", DietCode::LowLactose, "Vähälaktoosinen", "Low lactose"),
+    ("M", DietCode::MilkFree, "Maidoton", "Milk free"),
+    ("VEG", DietCode::Vegan, "Vegaaninen", "Vegan"),
+    ("K", DietCode::Vegetarian, "Kasvis", "Vegetarian"),
+    (
+        "*",
+        DietCode::Recommended,
+        "Ravitsemussuositusten mukainen",
+        "Follows the nutrition recommendations",
+    ),
+    (
+        "ILM",
+        DietCode::ClimateFriendly,
+        "Ilmastoystävällinen",
+        "Climate friendly",
+    ),
+    (
+        "VS",
+        DietCode::FreshGarlic,
+        "Sisältää tuoretta valkosipulia",
+        "Contains fresh garlic",
+    ),
+    (
+        "A",
+        DietCode::Allergens,
+        "Sisältää allergeeneja",
+        "Contains allergens",
+    ),
+];
+
+impl DietCode {
+    pub fn parse(code: &str) -> Self {
+        let code = code.trim();
+
+        CODES
+            .iter()
+            .find(|(c, ..)| c.eq_ignore_ascii_case(code))
+            .map(|(_, d, ..)| d.clone())
+            .unwrap_or(DietCode::Unknown(code.to_string()))
+    }
+
+    fn entry(&self) -> Option<&'static Code> {
+        CODES.iter().find(|(_, d, ..)| d == self)
+    }
+
+    pub fn code(&self) -> &str {
+        match self {
+            DietCode::Unknown(code) => code,
+            known => known.entry().map(|(c, ..)| *c).unwrap_or_default(),
+        }
+    }
+
+    pub fn description(&self, locale: Locale) -> &str {
+        match (self.entry(), locale) {
+            (Some((_, _, fi, _)), Locale::Fi) => fi,
+            (Some((_, _, _, en)), Locale::En) => en,
+            (None, _) => locale.strings().unknown_diet_code,
+        }
+    }
+}
+
+impl Display for DietCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// Parses a list like `G, M` or `L,G,VS`, every code is kept once
+pub fn parse_diet_codes(s: &str) -> Vec<DietCode> {
+    let mut codes = Vec::new();
+
+    for code in s.split([',', ' ']).filter(|c| !c.trim().is_empty()) {
+        let code = DietCode::parse(code);
+
+        if !codes.contains(&code) {
+            codes.push(code);
+        }
+    }
+
+    codes
+}
+
+/// Everything that can be said about a dish's suitability for a diet
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DietFlags {
+    pub gluten_free: bool,
+    pub lactose_free: bool,
+    pub milk_free: bool,
+    pub low_lactose: bool,
+
+    pub low_co2: bool,
+    /// The heart icon, a better choice within its category
+    pub better_choice: bool,
+    pub vegan: bool,
+    pub student_recommendation: bool,
+
+    pub pork: bool,
+    pub fi_meat: bool,
+    pub eu_meat: bool,
+    pub other_meat: bool,
+}
+
+impl From<&[DietCode]> for DietFlags {
+    fn from(codes: &[DietCode]) -> Self {
+        let has = |code: DietCode| codes.contains(&code);
+
+        DietFlags {
+            gluten_free: has(DietCode::GlutenFree),
+            lactose_free: has(DietCode::LactoseFree),
+            milk_free: has(DietCode::MilkFree),
+            low_lactose: has(DietCode::LowLactose),
+            low_co2: has(DietCode::ClimateFriendly),
+            vegan: has(DietCode::Vegan),
+            ..Default::default()
+        }
+    }
+}
+
+/// Sodexo marks some of the same things with both a code and an icon
+impl From<(&DietInfo, &FoodInfo)> for DietFlags {
+    fn from((diet, food): (&DietInfo, &FoodInfo)) -> Self {
+        let codes = DietFlags::from(diet.codes.as_slice());
+
+        DietFlags {
+            low_co2: codes.low_co2 || food.co2,
+            better_choice: codes.better_choice || food.heart,
+            vegan: codes.vegan || food.vegan,
+            student_recommendation: food.student_recommendation,
+            pork: food.pork,
+            fi_meat: food.fi_meat,
+            eu_meat: food.eu_meat,
+            other_meat: food.other_meat,
+            ..codes
+        }
+    }
+}
+
+/// Restrictions a dish has to meet to be shown, everything is shown by default
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DietFilter {
+    pub vegan: bool,
+    pub gluten_free: bool,
+    pub milk_free: bool,
+    pub lactose_free: bool,
+    pub no_pork: bool,
+    pub fi_meat_only: bool,
+    pub low_co2: bool,
+}
+
+impl DietFilter {
+    fn fields(&self) -> [bool; 7] {
+        [
+            self.vegan,
+            self.gluten_free,
+            self.milk_free,
+            self.lactose_free,
+            self.no_pork,
+            self.fi_meat_only,
+            self.low_co2,
+        ]
+    }
+
+    /// The filter as a number for button ids, read back with [`DietFilter::from_bits`]
+    pub fn bits(&self) -> u8 {
+        self.fields()
+            .iter()
+            .enumerate()
+            .map(|(i, on)| u8::from(*on) << i)
+            .sum()
+    }
+
+    pub fn from_bits(bits: u8) -> Self {
+        let on = |i: u8| bits & (1 << i) != 0;
+
+        DietFilter {
+            vegan: on(0),
+            gluten_free: on(1),
+            milk_free: on(2),
+            lactose_free: on(3),
+            no_pork: on(4),
+            fi_meat_only: on(5),
+            low_co2: on(6),
+        }
+    }
+
+    pub fn set(&mut self, restriction: DietRestriction, on: bool) {
+        let field = match restriction {
+            DietRestriction::Vegan => &mut self.vegan,
+            DietRestriction::GlutenFree => &mut self.gluten_free,
+            DietRestriction::MilkFree => &mut self.milk_free,
+            DietRestriction::LactoseFree => &mut self.lactose_free,
+            DietRestriction::NoPork => &mut self.no_pork,
+            DietRestriction::FiMeatOnly => &mut self.fi_meat_only,
+            DietRestriction::LowCo2 => &mut self.low_co2,
+        };
+        *field = on;
+    }
+
+    pub fn matches(&self, diet: &DietFlags) -> bool {
+        self.unmet(diet) == DietFilter::default()
+    }
+
+    /// The restrictions `diet` doesn't meet. A vegan dish is also milk free and
+    /// a milk free one lactose free, even when the caterer only marked the stricter one.
+    pub fn unmet(&self, diet: &DietFlags) -> DietFilter {
+        let milk_free = diet.milk_free || diet.vegan;
+        let lactose_free = diet.lactose_free || milk_free;
+
+        DietFilter {
+            vegan: self.vegan && !diet.vegan,
+            gluten_free: self.gluten_free && !diet.gluten_free,
+            milk_free: self.milk_free && !milk_free,
+            lactose_free: self.lactose_free && !lactose_free,
+            no_pork: self.no_pork && diet.pork,
+            fi_meat_only: self.fi_meat_only && (diet.eu_meat || diet.other_meat),
+            low_co2: self.low_co2 && !diet.low_co2,
+        }
+    }
+
+    /// Names of the restrictions that are on
+    pub fn labels(&self, locale: Locale) -> Vec<&'static str> {
+        let t = locale.strings();
+        let labels = [
+            t.vegan,
+            t.gluten_free,
+            t.milk_free,
+            t.lactose_free,
+            t.no_pork,
+            t.fi_meat_only,
+            t.low_co2,
+        ];
+
+        labels
+            .into_iter()
+            .zip(self.fields())
+            .filter_map(|(label, on)| on.then_some(label))
+            .collect()
+    }
+}
+
+/// One restriction of a [`DietFilter`], the menu and profile commands take these as options
+#[derive(Debug, Clone, Copy, PartialEq, Eq, poise::ChoiceParameter)]
+pub enum DietRestriction {
+    #[name = "Vegaaninen"]
+    #[name_localized("en-US", "Vegan")]
+    #[name_localized("en-GB", "Vegan")]
+    Vegan,
+    #[name = "Gluteeniton"]
+    #[name_localized("en-US", "Gluten free")]
+    #[name_localized("en-GB", "Gluten free")]
+    GlutenFree,
+    #[name = "Maidoton"]
+    #[name_localized("en-US", "Milk free")]
+    #[name_localized("en-GB", "Milk free")]
+    MilkFree,
+    #[name = "Laktoositon"]
+    #[name_localized("en-US", "Lactose free")]
+    #[name_localized("en-GB", "Lactose free")]
+    LactoseFree,
+    #[name = "Ei porsasta"]
+    #[name_localized("en-US", "No pork")]
+    #[name_localized("en-GB", "No pork")]
+    NoPork,
+    /// Only applies to dishes with meat
+    #[name = "Vain suomalaista lihaa"]
+    #[name_localized("en-US", "Only Finnish meat")]
+    #[name_localized("en-GB", "Only Finnish meat")]
+    FiMeatOnly,
+    #[name = "Vähäpäästöinen (=< 0.5 kg CO2)"]
+    #[name_localized("en-US", "Low emission (=< 0.5 kg CO2)")]
+    #[name_localized("en-GB", "Low emission (=< 0.5 kg CO2)")]
+    LowCo2,
+}
+
+impl FromIterator<DietRestriction> for DietFilter {
+    fn from_iter<I: IntoIterator<Item = DietRestriction>>(iter: I) -> Self {
+        let mut filter = DietFilter::default();
+        for restriction in iter {
+            filter.set(restriction, true);
+        }
+
+        filter
+    }
+}
+
+#[test]
+fn diet_codes() {
+    assert_eq!(
+        parse_diet_codes("G, M"),
+        vec![DietCode::GlutenFree, DietCode::MilkFree]
+    );
+    assert_eq!(
+        parse_diet_codes(" l,g ,VS, *, Veg, XY, G"),
+        vec![
+            DietCode::LactoseFree,
+            DietCode::GlutenFree,
+            DietCode::FreshGarlic,
+            DietCode::Recommended,
+            DietCode::Vegan,
+            DietCode::Unknown("XY".to_string()),
+        ]
+    );
+    assert!(parse_diet_codes(" , ").is_empty());
+
+    assert_eq!(DietCode::parse("vl").code(), "VL");
+    assert_eq!(DietCode::parse("XY").to_string(), "XY");
+    assert_eq!(
+        DietCode::parse("A").description(Locale::Fi),
+        "Sisältää allergeeneja"
+    );
+    assert_eq!(
+        DietCode::parse("A").description(Locale::En),
+        "Contains allergens"
+    );
+
+    let flags = DietFlags::from(parse_diet_codes("M, G, *").as_slice());
+    assert!(flags.milk_free && flags.gluten_free);
+    // `*` is only a code, the better choice comes from the heart icon
+    assert!(!flags.better_choice);
+    assert!(!flags.lactose_free);
+}
+
+#[test]
+fn diet_filters() {
+    let mut filter = [DietRestriction::Vegan, DietRestriction::LowCo2]
+        .into_iter()
+        .collect::<DietFilter>();
+    assert_eq!(
+        filter,
+        DietFilter {
+            vegan: true,
+            low_co2: true,
+            ..Default::default()
+        }
+    );
+    filter.set(DietRestriction::Vegan, false);
+    assert_eq!(filter.labels(Locale::En), vec!["=< 0.5 kg CO2 emissions"]);
+
+    let vegan = DietFlags {
+        vegan: true,
+        gluten_free: true,
+        ..Default::default()
+    };
+    let pork = DietFlags {
+        pork: true,
+        fi_meat: true,
+        lactose_free: true,
+        ..Default::default()
+    };
+    let beef = DietFlags {
+        other_meat: true,
+        milk_free: true,
+        low_co2: true,
+        ..Default::default()
+    };
+
+    let filter = |f: DietFilter| {
+        [vegan, pork, beef]
+            .iter()
+            .map(|d| f.matches(d))
+            .collect::<Vec<bool>>()
+    };
+
+    assert_eq!(filter(DietFilter::default()), vec![true, true, true]);
+    let lactose_free = DietFilter {
+        lactose_free: true,
+        ..Default::default()
+    };
+    assert_eq!(filter(lactose_free), vec![true, true, true]);
+    let milk_free = DietFilter {
+        milk_free: true,
+        ..Default::default()
+    };
+    assert_eq!(filter(milk_free), vec![true, false, true]);
+    let no_pork = DietFilter {
+        no_pork: true,
+        ..Default::default()
+    };
+    assert_eq!(filter(no_pork), vec![true, false, true]);
+    let fi_meat = DietFilter {
+        fi_meat_only: true,
+        ..Default::default()
+    };
+    assert_eq!(filter(fi_meat), vec![true, true, false]);
+    let strict = DietFilter {
+        gluten_free: true,
+        low_co2: true,
+        ..Default::default()
+    };
+    assert_eq!(filter(strict), vec![false, false, false]);
+
+    assert_eq!(
+        strict.unmet(&vegan),
+        DietFilter {
+            low_co2: true,
+            ..Default::default()
+        }
+    );
+    assert_eq!(
+        strict.unmet(&vegan).labels(Locale::En),
+        vec!["=< 0.5 kg CO2 emissions"]
+    );
+
+    for bits in [0, 1, 0b101_0101, 0b111_1111] {
+        assert_eq!(DietFilter::from_bits(bits).bits(), bits);
+    }
+}
+
+#[test]
+fn diet_codes_in_fixtures() {
+    use crate::types::day::DailyMenu;
+    use crate::types::week::WeeklyMenu;
+
+    let daily = std::fs::read_to_string("tests/daily.json").expect("no file");
+    let daily: DailyMenu = serde_json::from_str(&daily).expect("unable to parse json");
+    let weekly = std::fs::read_to_string("tests/weekly.json").expect("no file");
+    let weekly: WeeklyMenu = serde_json::from_str(&weekly).expect("unable to parse json");
+
+    let courses = daily
+        .courses
+        .iter()
+        .chain(weekly.mealdates.iter().flat_map(|d| &d.courses))
+        .map(|(_, c)| c)
+        .collect::<Vec<_>>();
+
+    // `G, M` used to lose everything after the first code
+    assert!(
+        courses.iter().any(|c| c.diet_info.codes.len() == 2
+            && DietFlags::from(c.diet_info.codes.as_slice()).milk_free)
+    );
+
+    for course in courses {
+        assert!(
+            course
+                .diet_info
+                .codes
+                .iter()
+                .all(|c| !matches!(c, DietCode::Unknown(_)))
+        );
+
+        // properties repeat the codes
+        for code in &course.properties.codes {
+            assert!(course.diet_info.codes.contains(code));
+        }
+
+        // hideAll can disagree with the codes, but its codes are still known ones
+        if let Some(hide_all) = course.recipes.as_ref().and_then(|r| r.hide_all.as_ref()) {
+            assert!(
+                hide_all
+                    .diet_info
+                    .codes
+                    .iter()
+                    .all(|c| !matches!(c, DietCode::Unknown(_)))
+            );
+        }
+    }
+}
@@ -0,0 +1,279 @@
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// One entry of a recipe's ingredient list, products list what they are made
+/// of in square brackets: `majoneesi[Rypsiöljy 51 %, vesi, SINAPPI (...)] (13%)`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Ingredient {
+    pub name: String,
+    /// Share of the recipe like `48%`, from the `(48%)` after the name
+    pub share: Option<String>,
+    /// Words written in capitals, the way allergens are marked, like `VEHNÄ` in `mallasVEHNÄjauho`
+    pub allergens: Vec<String>,
+    pub parts: Vec<Ingredient>,
+}
+
+impl Ingredient {
+    fn new(text: &str, parts: Vec<Ingredient>) -> Self {
+        let text = text.trim();
+
+        let (name, share) = match text.strip_suffix(')').and_then(|t| t.rsplit_once('(')) {
+            Some((name, share)) if share.trim().ends_with('%') => {
+                (name.trim(), Some(share.trim().to_string()))
+            }
+            _ => (text, None),
+        };
+        // text on both sides of a bracket, `JUUSTO [...] ja ...`, leaves double spaces
+        let name = name
+            .trim_end_matches('.')
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ");
+
+        Ingredient {
+            allergens: capitalized_words(&name),
+            name,
+            share,
+            parts,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.name.is_empty() && self.parts.is_empty()
+    }
+}
+
+/// Runs of at least three capital letters, shorter ones are things like `E500` or `NaCl`
+fn capitalized_words(s: &str) -> Vec<String> {
+    let mut words: Vec<String> = Vec::new();
+    let mut word = String::new();
+
+    for c in s.chars().chain([' ']) {
+        if c.is_uppercase() {
+            word.push(c);
+            continue;
+        }
+
+        if word.chars().count() >= 3 && !words.contains(&word) {
+            words.push(word.clone());
+        }
+        word.clear();
+    }
+
+    words
+}
+
+/// Parses a whole ingredient list. The lists are typed by hand so brackets
+/// left open are closed at the end, `]` without an open bracket is skipped and
+/// empty `[]` are skipped.
+pub fn parse_ingredients(s: &str) -> Vec<Ingredient> {
+    parse_list(&mut s.chars().peekable(), 0)
+}
+
+/// `depth` is the number of open square brackets around the list
+fn parse_list(chars: &mut Peekable<Chars>, depth: usize) -> Vec<Ingredient> {
+    let mut list = Vec::new();
+
+    loop {
+        let ingredient = parse_ingredient(chars, depth);
+        if !ingredient.is_empty() {
+            list.push(ingredient);
+        }
+
+        match chars.next() {
+            Some(',') => continue,
+            // `]` or the end of the list
+            _ => return list,
+        }
+    }
+}
+
+/// Reads up to the next `,` or `]` outside of parentheses, commas in
+/// `jauhot (kikherne, herne)` belong to the name
+fn parse_ingredient(chars: &mut Peekable<Chars>, depth: usize) -> Ingredient {
+    let mut text = String::new();
+    let mut parts = Vec::new();
+    let mut parens = 0usize;
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            ']' if depth > 0 => break,
+            ']' => {
+                chars.next();
+                continue;
+            }
+            ',' if parens == 0 => break,
+            '[' => {
+                chars.next();
+                parts.extend(parse_list(chars, depth + 1));
+                continue;
+            }
+            '(' => parens += 1,
+            ')' => parens = parens.saturating_sub(1),
+            _ => (),
+        }
+
+        text.push(c);
+        chars.next();
+    }
+
+    Ingredient::new(&text, parts)
+}
+
+/// Every ingredient of the tree, parts included
+pub fn flatten(ingredients: &[Ingredient]) -> Vec<&Ingredient> {
+    let mut all = Vec::new();
+    let mut stack = ingredients.iter().rev().collect::<Vec<&Ingredient>>();
+
+    while let Some(i) = stack.pop() {
+        all.push(i);
+        stack.extend(i.parts.iter().rev());
+    }
+
+    all
+}
+
+/// Nested markdown list with the allergens in bold
+pub fn fmt_ingredients(ingredients: &[Ingredient]) -> String {
+    let mut out = String::new();
+    write_list(&mut out, ingredients, 0);
+
+    out
+}
+
+/// Bolds the runs of capitals that are allergens, a run is bolded once even
+/// when a shorter allergen like `MAITO` is a part of a longer one
+fn bold_allergens(name: &str, allergens: &[String]) -> String {
+    let mut out = String::new();
+    let mut word = String::new();
+
+    for c in name.chars().map(Some).chain([None]) {
+        if let Some(c) = c
+            && c.is_uppercase()
+        {
+            word.push(c);
+            continue;
+        }
+
+        match allergens.contains(&word) {
+            true => out.push_str(&format!("**{word}**")),
+            false => out.push_str(&word),
+        }
+        word.clear();
+        out.extend(c);
+    }
+
+    out
+}
+
+fn write_list(out: &mut String, ingredients: &[Ingredient], depth: usize) {
+    for i in ingredients {
+        out.push_str(&"  ".repeat(depth));
+        out.push_str("- ");
+        out.push_str(&bold_allergens(&i.name, &i.allergens));
+        if let Some(share) = &i.share {
+            out.push_str(&format!(" ({share})"));
+        }
+        out.push('\n');
+
+        write_list(out, &i.parts, depth + 1);
+    }
+}
+
+#[test]
+fn ingredient_tree() {
+    let ingredients = parse_ingredients(
+        "patonki[VEHNÄjauho, vesi, mallasVEHNÄjauho, jauhonparanne (askorbiinihappo). ] (48%), öljy rapsi 10l[] (2%), juusto muru[JUUSTO [pastöroitu MAITO, suola] ja paakkuuntumisenestoaine (E 460).] (2%), jauhot (kikherne, herne), Vesi (38%)",
+    );
+
+    assert_eq!(ingredients.len(), 5);
+
+    let bread = &ingredients[0];
+    assert_eq!(bread.name, "patonki");
+    assert_eq!(bread.share.as_deref(), Some("48%"));
+    assert_eq!(bread.parts.len(), 4);
+    assert_eq!(bread.parts[0].allergens, vec!["VEHNÄ".to_string()]);
+    assert_eq!(bread.parts[2].name, "mallasVEHNÄjauho");
+    assert_eq!(bread.parts[3].name, "jauhonparanne (askorbiinihappo)");
+
+    assert_eq!(ingredients[1].name, "öljy rapsi 10l");
+    assert!(ingredients[1].parts.is_empty());
+
+    let cheese = &ingredients[2].parts[0];
+    assert_eq!(cheese.name, "JUUSTO ja paakkuuntumisenestoaine (E 460)");
+    assert_eq!(cheese.allergens, vec!["JUUSTO".to_string()]);
+    assert_eq!(cheese.parts[0].allergens, vec!["MAITO".to_string()]);
+
+    let names = flatten(&ingredients[2..3])
+        .iter()
+        .map(|i| i.name.as_str())
+        .collect::<Vec<&str>>();
+    assert_eq!(
+        names[1..],
+        [
+            "JUUSTO ja paakkuuntumisenestoaine (E 460)",
+            "pastöroitu MAITO",
+            "suola"
+        ]
+    );
+
+    assert_eq!(ingredients[3].name, "jauhot (kikherne, herne)");
+    assert_eq!(ingredients[3].share, None);
+
+    assert_eq!(
+        fmt_ingredients(&ingredients[2..3]),
+        "- juusto muru (2%)\n  - **JUUSTO** ja paakkuuntumisenestoaine (E 460)\n    - pastöroitu **MAITO**\n    - suola\n"
+    );
+}
+
+#[test]
+fn stray_brackets() {
+    let names = |s: &str| {
+        parse_ingredients(s)
+            .iter()
+            .map(|i| i.name.clone())
+            .collect::<Vec<String>>()
+    };
+
+    assert_eq!(names("A], B, C"), ["A", "B", "C"]);
+    assert_eq!(names("A[x]], B, C"), ["A", "B", "C"]);
+    assert_eq!(parse_ingredients("A[x]], B")[0].parts.len(), 1);
+}
+
+#[test]
+fn bold_allergens_once() {
+    let ingredients = parse_ingredients("MAITOPROTEIINI ja MAITO, kerma (MAITO)");
+    assert_eq!(
+        fmt_ingredients(&ingredients),
+        "- **MAITOPROTEIINI** ja **MAITO**\n- kerma (**MAITO**)\n"
+    );
+}
+
+#[test]
+fn ingredients_in_fixtures() {
+    use crate::types::day::DailyMenu;
+
+    let daily = std::fs::read_to_string("tests/2025-09-02.json").expect("no file");
+    let menu: DailyMenu = serde_json::from_str(&daily).expect("unable to parse json");
+
+    for (_, course) in menu.courses {
+        for recipe in course.recipes.map(|r| r.recipes).unwrap_or_default() {
+            let Some(raw) = recipe.ingredients.into_option() else {
+                continue;
+            };
+
+            // left open brackets don't lose the rest of the list
+            let ingredients = parse_ingredients(&raw);
+            assert!(!ingredients.is_empty());
+
+            // every capitalized word in the raw list ends up as an allergen somewhere
+            let found = flatten(&ingredients)
+                .into_iter()
+                .flat_map(|i| i.allergens.iter().cloned())
+                .collect::<Vec<String>>();
+            for word in capitalized_words(&raw) {
+                assert!(found.contains(&word), "{word} missing");
+            }
+        }
+    }
+}
@@ -0,0 +1,159 @@
+pub mod allergen;
+pub mod category;
+pub mod diet;
+pub mod ingredient;
+pub mod nutrition;
+pub mod price;
+
+pub use allergen::Allergens;
+pub use category::Category;
+pub use category::CourseOrder;
+pub use diet::DietCode;
+pub use diet::DietFilter;
+pub use diet::DietFlags;
+pub use diet::DietRestriction;
+pub use ingredient::Ingredient;
+pub use nutrition::Nutrition;
+pub use price::Price;
+pub use price::PriceTier;
+
+use crate::dates::weekday_name;
+use crate::locale::Locale;
+use chrono::Datelike;
+use chrono::NaiveDate;
+
+/// A single day's menu, independent of the caterer it came from
+#[derive(Debug, Clone)]
+pub struct Menu {
+    pub title: String,
+    pub url: String,
+    /// Unix timestamp, 0 if the caterer doesn't tell
+    pub generated_timestamp: i64,
+    pub restaurant_mashie_id: Option<String>,
+    pub dishes: Vec<Dish>,
+}
+
+impl Menu {
+    pub fn dish(&self, id: &str) -> Option<&Dish> {
+        self.dishes.iter().find(|d| d.id == id)
+    }
+
+    /// Dishes come in the restaurant's order, sorting by category keeps
+    /// that order within each category
+    pub fn sort_dishes(&mut self, order: CourseOrder) {
+        match order {
+            CourseOrder::Restaurant => (),
+            CourseOrder::Category => self.dishes.sort_by_key(|d| d.category),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Dish {
+    /// Stable within the day, used to refer back to the dish from buttons
+    pub id: String,
+    pub title_fi: String,
+    pub title_en: Option<String>,
+    pub category: Category,
+    pub price: Price,
+    pub diet: DietFlags,
+    /// Every code as the caterer wrote them, including ones [`DietFlags`] has no place for
+    pub diet_codes: Vec<DietCode>,
+    pub allergens: Allergens,
+    pub recipes: Vec<Recipe>,
+}
+
+impl Dish {
+    /// The english title falls back to the finnish one, not every dish has one
+    pub fn title(&self, locale: Locale) -> &str {
+        match (locale, &self.title_en) {
+            (Locale::En, Some(en)) => en,
+            _ => &self.title_fi,
+        }
+    }
+
+    /// Lowercase titles and names of everything in the dish, to look for
+    /// ingredients in. Allergens are included for the ones that aren't a known kind,
+    /// like `Herneet`.
+    pub fn names(&self) -> Vec<String> {
+        self.recipes
+            .iter()
+            .flat_map(|r| ingredient::flatten(&r.ingredients))
+            .map(|i| i.name.to_lowercase())
+            .chain(self.allergens.iter().map(|a| a.name.to_lowercase()))
+            .chain([
+                self.title_fi.to_lowercase(),
+                self.title_en.clone().unwrap_or_default().to_lowercase(),
+            ])
+            .collect()
+    }
+
+    /// Nutrients of every recipe added together, `None` if no recipe lists them
+    pub fn nutrition(&self) -> Option<Nutrition> {
+        let recipes = self.recipes.iter().filter_map(|r| r.nutrition.as_ref());
+
+        Some(Nutrition::total(recipes)).filter(|n| !n.is_empty())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Recipe {
+    pub name: String,
+    pub ingredients: Vec<Ingredient>,
+    pub nutrition: Option<Nutrition>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Week {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub days: Vec<WeekDay>,
+}
+
+#[derive(Debug, Clone)]
+pub struct WeekDay {
+    pub date: NaiveDate,
+    /// The caterer's name for the day, like `Maanantai`
+    pub name: String,
+    pub menu: Menu,
+}
+
+impl WeekDay {
+    /// Finnish keeps the caterer's own name for the day
+    pub fn label(&self, locale: Locale) -> String {
+        match locale {
+            Locale::Fi => self.name.clone(),
+            Locale::En => weekday_name(self.date.weekday(), locale),
+        }
+    }
+}
+
+#[test]
+fn sort_by_category() {
+    use crate::types::day::DailyMenu;
+
+    let daily = std::fs::read_to_string("tests/daily.json").expect("no file");
+    let menu: DailyMenu = serde_json::from_str(&daily).expect("unable to parse json");
+    let mut menu: Menu = menu.into();
+
+    let ids = |m: &Menu| {
+        m.dishes
+            .iter()
+            .map(|d| d.id.clone())
+            .collect::<Vec<String>>()
+    };
+    let restaurant_order = ids(&menu);
+    assert!(
+        restaurant_order
+            .iter()
+            .map(|id| id.parse::<u32>().expect("non numeric id"))
+            .is_sorted()
+    );
+
+    menu.sort_dishes(CourseOrder::Restaurant);
+    assert_eq!(ids(&menu), restaurant_order);
+
+    menu.sort_dishes(CourseOrder::Category);
+    assert!(menu.dishes.iter().map(|d| d.category).is_sorted());
+    assert_eq!(menu.dishes[0].category, Category::Main);
+}
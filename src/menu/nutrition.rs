@@ -0,0 +1,260 @@
+use crate::locale::Locale;
+
+/// The nutrients caterers list for every recipe, in the order they are shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Nutrient {
+    EnergyKj,
+    EnergyKcal,
+    Fat,
+    Carbohydrate,
+    Protein,
+    Fibre,
+    Salt,
+}
+
+impl Nutrient {
+    pub const ALL: [Nutrient; 7] = [
+        Nutrient::EnergyKj,
+        Nutrient::EnergyKcal,
+        Nutrient::Fat,
+        Nutrient::Carbohydrate,
+        Nutrient::Protein,
+        Nutrient::Fibre,
+        Nutrient::Salt,
+    ];
+
+    pub fn label(self, locale: Locale) -> &'static str {
+        match (self, locale) {
+            (Nutrient::EnergyKj | Nutrient::EnergyKcal, Locale::Fi) => "Energia",
+            (Nutrient::EnergyKj | Nutrient::EnergyKcal, Locale::En) => "Energy",
+            (Nutrient::Fat, Locale::Fi) => "Rasva",
+            (Nutrient::Fat, Locale::En) => "Fat",
+            (Nutrient::Carbohydrate, Locale::Fi) => "Hiilihydraatit",
+            (Nutrient::Carbohydrate, Locale::En) => "Carbohydrate",
+            (Nutrient::Protein, Locale::Fi) => "Proteiini",
+            (Nutrient::Protein, Locale::En) => "Protein",
+            (Nutrient::Fibre, Locale::Fi) => "Kuitu",
+            (Nutrient::Fibre, Locale::En) => "Fibre",
+            (Nutrient::Salt, Locale::Fi) => "Suola",
+            (Nutrient::Salt, Locale::En) => "Salt",
+        }
+    }
+
+    pub fn unit(self) -> &'static str {
+        match self {
+            Nutrient::EnergyKj => "kJ",
+            Nutrient::EnergyKcal => "kcal",
+            _ => "g",
+        }
+    }
+
+    /// Matches a label like `Rasva/Fat` and the unit of its value, energy is
+    /// listed twice and only the unit tells the two apart
+    fn parse(label: &str, unit: &str) -> Option<Self> {
+        let label = label.to_lowercase();
+        let is = |names: &[&str]| label.split('/').any(|l| names.contains(&l.trim()));
+
+        let nutrient = if is(&["energia", "energy"]) {
+            match unit.to_lowercase().as_str() {
+                "kj" => Nutrient::EnergyKj,
+                "kcal" => Nutrient::EnergyKcal,
+                _ => return None,
+            }
+        } else if is(&["rasva", "fat"]) {
+            Nutrient::Fat
+        } else if is(&[
+            "hiilihydraatit",
+            "hiilihydraatti",
+            "carbohydrate",
+            "carbohydrates",
+        ]) {
+            Nutrient::Carbohydrate
+        } else if is(&["proteiinit", "proteiini", "protein"]) {
+            Nutrient::Protein
+        } else if is(&["kuidut", "kuitu", "ravintokuitu", "fibre", "fiber"]) {
+            Nutrient::Fibre
+        } else if is(&["suola", "salt"]) {
+            Nutrient::Salt
+        } else {
+            return None;
+        };
+
+        (nutrient.unit().eq_ignore_ascii_case(unit)).then_some(nutrient)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Nutrition {
+    pub energy_kj: Option<f64>,
+    pub energy_kcal: Option<f64>,
+    /// Grams, like the rest
+    pub fat: Option<f64>,
+    pub carbohydrate: Option<f64>,
+    pub protein: Option<f64>,
+    pub fibre: Option<f64>,
+    pub salt: Option<f64>,
+    /// Anything not recognized as `(label, value)`, kept as sent
+    pub extras: Vec<(String, String)>,
+}
+
+impl Nutrition {
+    /// Parses sodexo's `Energia/Energy: 463.67 kJ | Rasva/Fat: 4.86 g` format
+    pub fn parse(s: &str) -> Self {
+        let mut nutrition = Nutrition::default();
+
+        for n in s.split('|').map(str::trim).filter(|n| !n.is_empty()) {
+            let (label, value) = match n.split_once(':') {
+                Some((label, value)) => (label.trim(), value.trim()),
+                None => ("", n),
+            };
+
+            let parsed = value.split_once(' ').and_then(|(amount, unit)| {
+                let amount = amount.replace(',', ".").parse::<f64>().ok()?;
+                let nutrient = Nutrient::parse(label, unit.trim())?;
+
+                Some((nutrient, amount))
+            });
+
+            match parsed {
+                Some((nutrient, amount)) => *nutrition.get_mut(nutrient) = Some(amount),
+                None => nutrition
+                    .extras
+                    .push((label.to_string(), value.to_string())),
+            }
+        }
+
+        nutrition
+    }
+
+    pub fn get(&self, nutrient: Nutrient) -> Option<f64> {
+        match nutrient {
+            Nutrient::EnergyKj => self.energy_kj,
+            Nutrient::EnergyKcal => self.energy_kcal,
+            Nutrient::Fat => self.fat,
+            Nutrient::Carbohydrate => self.carbohydrate,
+            Nutrient::Protein => self.protein,
+            Nutrient::Fibre => self.fibre,
+            Nutrient::Salt => self.salt,
+        }
+    }
+
+    fn get_mut(&mut self, nutrient: Nutrient) -> &mut Option<f64> {
+        match nutrient {
+            Nutrient::EnergyKj => &mut self.energy_kj,
+            Nutrient::EnergyKcal => &mut self.energy_kcal,
+            Nutrient::Fat => &mut self.fat,
+            Nutrient::Carbohydrate => &mut self.carbohydrate,
+            Nutrient::Protein => &mut self.protein,
+            Nutrient::Fibre => &mut self.fibre,
+            Nutrient::Salt => &mut self.salt,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        Nutrient::ALL.iter().all(|n| self.get(*n).is_none()) && self.extras.is_empty()
+    }
+
+    /// Sum of the known nutrients, a nutrient missing from every recipe stays
+    /// missing. Extras can't be added up and are left out.
+    pub fn total<'a>(recipes: impl IntoIterator<Item = &'a Nutrition>) -> Self {
+        let mut total = Nutrition::default();
+
+        for recipe in recipes {
+            for nutrient in Nutrient::ALL {
+                if let Some(amount) = recipe.get(nutrient) {
+                    *total.get_mut(nutrient).get_or_insert(0.0) += amount;
+                }
+            }
+        }
+
+        total
+    }
+
+    /// One nutrient per line with the amounts aligned, meant for a code block
+    pub fn table(&self, locale: Locale) -> String {
+        let rows = Nutrient::ALL
+            .iter()
+            .filter_map(|n| {
+                self.get(*n)
+                    .map(|amount| (n.label(locale), format!("{amount:>8.2} {}", n.unit())))
+            })
+            .chain(
+                self.extras
+                    .iter()
+                    .map(|(label, value)| (label.as_str(), value.clone())),
+            )
+            .collect::<Vec<(&str, String)>>();
+
+        let width = rows
+            .iter()
+            .map(|(label, _)| label.chars().count())
+            .max()
+            .unwrap_or_default();
+
+        rows.into_iter()
+            .map(|(label, value)| match label.is_empty() {
+                true => format!("{value}\n"),
+                false => format!("{label:<width$} {value}\n"),
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn nutrition_values() {
+    let nutrition = Nutrition::parse(
+        "Energia/Energy: 463.67 kJ | Energia/Energy: 110.67 kcal | Rasva/Fat: 4.86 g | Josta tyydyttyneet/Saturates: 1,2 g | Suola/Salt: 0.32 g",
+    );
+
+    assert_eq!(nutrition.energy_kj, Some(463.67));
+    assert_eq!(nutrition.energy_kcal, Some(110.67));
+    assert_eq!(nutrition.get(Nutrient::Fat), Some(4.86));
+    assert_eq!(nutrition.protein, None);
+    assert_eq!(
+        nutrition.extras,
+        vec![(
+            "Josta tyydyttyneet/Saturates".to_string(),
+            "1,2 g".to_string()
+        )]
+    );
+
+    assert_eq!(
+        nutrition.table(Locale::Fi),
+        r"Energia                        463.67 kJ
+Energia                        110.67 kcal
+Rasva                            4.86 g
+Suola                            0.32 g
+Josta tyydyttyneet/Saturates 1,2 g
+"
+    );
+
+    assert!(Nutrition::parse("").is_empty());
+}
+
+#[test]
+fn nutrition_totals() {
+    use crate::types::day::DailyMenu;
+
+    let daily = std::fs::read_to_string("tests/2025-09-02.json").expect("no file");
+    let menu: DailyMenu = serde_json::from_str(&daily).expect("unable to parse json");
+
+    let recipes = menu
+        .courses
+        .iter()
+        .flat_map(|(_, c)| c.recipes.iter().flat_map(|r| &r.recipes))
+        .map(|r| Nutrition::parse(&r.nutrients))
+        .collect::<Vec<Nutrition>>();
+
+    // every recipe in the fixture has all of the nutrients
+    for recipe in &recipes {
+        assert!(recipe.extras.is_empty());
+        assert!(Nutrient::ALL.iter().all(|n| recipe.get(*n).is_some()));
+    }
+
+    let total = Nutrition::total(&recipes);
+    let kcal = recipes.iter().filter_map(|r| r.energy_kcal).sum::<f64>();
+    assert_eq!(total.energy_kcal, Some(kcal));
+    assert!(total.energy_kj > total.energy_kcal);
+
+    assert_eq!(Nutrition::total([]).salt, None);
+}
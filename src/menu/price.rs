@@ -0,0 +1,189 @@
+use crate::locale::Locale;
+use poise::ChoiceParameter;
+use serde::Deserialize;
+use serde::Serialize;
+use std::fmt::Display;
+
+/// Who the price applies to, listed in the order caterers print them
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, poise::ChoiceParameter,
+)]
+pub enum PriceTier {
+    #[name = "Opiskelija"]
+    #[name_localized("en-US", "Student")]
+    #[name_localized("en-GB", "Student")]
+    Student,
+    #[name = "Henkilökunta"]
+    #[name_localized("en-US", "Staff")]
+    #[name_localized("en-GB", "Staff")]
+    Staff,
+    #[name = "Vierailija"]
+    #[name_localized("en-US", "Guest")]
+    #[name_localized("en-GB", "Guest")]
+    Guest,
+}
+
+impl PriceTier {
+    pub fn label(self, locale: Locale) -> &'static str {
+        match locale {
+            Locale::Fi => self.name(),
+            Locale::En => self.localized_name("en-US").unwrap_or(self.name()),
+        }
+    }
+}
+
+/// An amount in euro cents
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Amount(pub u32);
+
+impl Amount {
+    /// Accepts `2,95 €`, `2.95€`, `3 €` and `2,5`
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim().trim_end_matches('€').trim();
+        let (euros, cents) = s.split_once([',', '.']).unwrap_or((s, "0"));
+
+        if euros.is_empty() || !euros.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        if cents.is_empty() || cents.len() > 2 || !cents.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+
+        let euros: u32 = euros.parse().ok()?;
+        let cents: u32 = match cents.len() {
+            1 => cents.parse::<u32>().ok()? * 10,
+            _ => cents.parse().ok()?,
+        };
+
+        euros.checked_mul(100)?.checked_add(cents).map(Amount)
+    }
+}
+
+impl Display for Amount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{:02} €", self.0 / 100, self.0 % 100)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tiers {
+    pub student: Amount,
+    pub staff: Amount,
+    pub guest: Amount,
+}
+
+impl Tiers {
+    pub fn get(&self, tier: PriceTier) -> Amount {
+        match tier {
+            PriceTier::Student => self.student,
+            PriceTier::Staff => self.staff,
+            PriceTier::Guest => self.guest,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum Price {
+    #[default]
+    Unknown,
+    /// The same price for everyone
+    Single(Amount),
+    Tiers(Tiers),
+    /// Anything that couldn't be parsed, shown as is
+    Text(String),
+}
+
+impl Price {
+    /// Prices come as `2,95 € / 7,00 € / 10,00 €`, student, staff and guest
+    pub fn parse(s: Option<&str>) -> Self {
+        let s = match s.map(str::trim) {
+            Some(s) if !s.is_empty() => s,
+            _ => return Price::Unknown,
+        };
+
+        let amounts = s
+            .split('/')
+            .map(Amount::parse)
+            .collect::<Option<Vec<Amount>>>();
+
+        match amounts.as_deref() {
+            Some([amount]) => Price::Single(*amount),
+            Some([student, staff, guest]) => Price::Tiers(Tiers {
+                student: *student,
+                staff: *staff,
+                guest: *guest,
+            }),
+            _ => Price::Text(s.to_string()),
+        }
+    }
+
+    /// The price for `tier` if it's known, every price otherwise
+    pub fn for_tier(&self, tier: Option<PriceTier>, locale: Locale) -> String {
+        match (self, tier) {
+            (Price::Tiers(tiers), Some(tier)) => {
+                format!("{} ({})", tiers.get(tier), tier.label(locale))
+            }
+            _ => self.to_string(),
+        }
+    }
+}
+
+impl Display for Price {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Price::Unknown => f.write_str("N/A"),
+            Price::Single(amount) => amount.fmt(f),
+            Price::Tiers(t) => write!(f, "{} / {} / {}", t.student, t.staff, t.guest),
+            Price::Text(s) => f.write_str(s),
+        }
+    }
+}
+
+#[test]
+fn prices() {
+    let tiers = Price::parse(Some("2,95 € / 7,00 € / 10,00 €"));
+    assert_eq!(
+        tiers,
+        Price::Tiers(Tiers {
+            student: Amount(295),
+            staff: Amount(700),
+            guest: Amount(1000),
+        })
+    );
+    assert_eq!(tiers.to_string(), "2,95 € / 7,00 € / 10,00 €");
+    assert_eq!(
+        tiers.for_tier(None, Locale::Fi),
+        "2,95 € / 7,00 € / 10,00 €"
+    );
+    assert_eq!(
+        tiers.for_tier(Some(PriceTier::Staff), Locale::Fi),
+        "7,00 € (Henkilökunta)"
+    );
+    assert_eq!(
+        tiers.for_tier(Some(PriceTier::Staff), Locale::En),
+        "7,00 € (Staff)"
+    );
+
+    // a typo'd decimal point in one of the fixtures
+    assert_eq!(
+        Price::parse(Some("2.95 € / 7,15 € / 10,00 €")),
+        Price::parse(Some("2,95 € / 7,15 € / 10,00 €"))
+    );
+
+    let single = Price::parse(Some("5,5€"));
+    assert_eq!(single, Price::Single(Amount(550)));
+    assert_eq!(
+        single.for_tier(Some(PriceTier::Student), Locale::Fi),
+        "5,50 €"
+    );
+
+    assert_eq!(Price::parse(Some(" ")), Price::Unknown);
+    assert_eq!(Price::parse(None).to_string(), "N/A");
+
+    let text = Price::parse(Some("2,95 € / kysy kassalta"));
+    assert_eq!(text, Price::Text("2,95 € / kysy kassalta".to_string()));
+    assert_eq!(
+        text.for_tier(Some(PriceTier::Guest), Locale::Fi),
+        "2,95 € / kysy kassalta"
+    );
+}
@@ -0,0 +1,381 @@
+use crate::Error;
+use crate::locale::Locale;
+use crate::menu::DietFilter;
+use crate::menu::Dish;
+use crate::menu::Ingredient;
+use crate::menu::allergen::AllergenKind;
+use crate::menu::ingredient::flatten;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs::read_to_string;
+use std::fs::write;
+
+const PROFILES_FILE: &str = "profiles.json";
+
+/// What a user can't or won't eat, set with `/profiili`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profile {
+    pub diet: DietFilter,
+    pub allergens: Vec<AllergenKind>,
+    /// Lowercase, matched anywhere in the dish's name or ingredients
+    pub ingredients: Vec<String>,
+    /// Leave conflicting dishes out instead of marking them
+    pub hide_conflicts: bool,
+    /// Lets `/ryhma` check dishes against the profile, the reasons a dish
+    /// doesn't suit are shown to the whole group
+    pub share_in_groups: bool,
+}
+
+/// Why a dish doesn't suit a profile
+#[derive(Debug, Clone, PartialEq)]
+pub enum Conflict {
+    /// The restrictions the dish doesn't meet
+    Diet(DietFilter),
+    /// An avoided allergen as the caterer named it, or as it's written in the
+    /// ingredients if the caterer left it out of the allergens
+    Allergen(String),
+    /// An avoided ingredient as the user wrote it
+    Ingredient(String),
+}
+
+impl Conflict {
+    pub fn describe(&self, locale: Locale) -> String {
+        match self {
+            Conflict::Diet(unmet) => unmet.labels(locale).join(", "),
+            Conflict::Allergen(name) | Conflict::Ingredient(name) => name.clone(),
+        }
+    }
+
+    /// Allergens and ingredients are something to warn about, not just a diet mismatch
+    pub fn is_warning(&self) -> bool {
+        !matches!(self, Conflict::Diet(_))
+    }
+}
+
+impl Profile {
+    /// No restrictions, the settings of how the profile is used don't count
+    pub fn is_empty(&self) -> bool {
+        self == &Profile {
+            hide_conflicts: self.hide_conflicts,
+            share_in_groups: self.share_in_groups,
+            ..Default::default()
+        }
+    }
+
+    pub fn conflicts(&self, dish: &Dish) -> Vec<Conflict> {
+        let mut conflicts = Vec::new();
+
+        let unmet = self.diet.unmet(&dish.diet);
+        if unmet != DietFilter::default() {
+            conflicts.push(Conflict::Diet(unmet));
+        }
+
+        conflicts.extend(
+            dish.allergens
+                .iter()
+                .filter(|a| self.allergens.contains(&a.kind))
+                .map(|a| Conflict::Allergen(a.name.clone())),
+        );
+
+        let ingredients = dish
+            .recipes
+            .iter()
+            .flat_map(|r| flatten(&r.ingredients))
+            .collect::<Vec<&Ingredient>>();
+
+        // capitalized words in the ingredients, for allergens missing from the dish's list
+        let mut found = dish.allergens.iter().map(|a| a.kind).collect::<Vec<_>>();
+        for word in ingredients.iter().flat_map(|i| &i.allergens) {
+            let kind = AllergenKind::parse(word);
+
+            if self.allergens.contains(&kind) && !found.contains(&kind) {
+                found.push(kind);
+                conflicts.push(Conflict::Allergen(word.clone()));
+            }
+        }
+
+        let names = dish.names();
+
+        conflicts.extend(
+            self.ingredients
+                .iter()
+                .filter(|avoided| names.iter().any(|n| n.contains(avoided.as_str())))
+                .map(|avoided| Conflict::Ingredient(avoided.clone())),
+        );
+
+        conflicts
+    }
+}
+
+/// Everything in [`Conflict::describe`] on one line
+pub fn describe_conflicts(conflicts: &[Conflict], locale: Locale) -> String {
+    conflicts
+        .iter()
+        .map(|c| c.describe(locale))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// How a dish suits a group, `conflicts` has the members it doesn't suit
+#[derive(Debug)]
+pub struct GroupFit<'a> {
+    pub dish: &'a Dish,
+    pub conflicts: Vec<(u64, Vec<Conflict>)>,
+}
+
+/// Checks every dish against every member's profile, in the order of `dishes`
+pub fn group_fit<'a>(dishes: &'a [Dish], members: &[(u64, Profile)]) -> Vec<GroupFit<'a>> {
+    dishes
+        .iter()
+        .map(|dish| GroupFit {
+            dish,
+            conflicts: members
+                .iter()
+                .map(|(id, profile)| (*id, profile.conflicts(dish)))
+                .filter(|(_, c)| !c.is_empty())
+                .collect(),
+        })
+        .collect()
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Profiles {
+    #[serde(default)]
+    pub users: HashMap<u64, Profile>,
+    /// The users who have used `/profiili` on each guild, so a role's members
+    /// can be found without going through the whole guild
+    #[serde(default)]
+    pub guilds: HashMap<u64, Vec<u64>>,
+}
+
+impl Profiles {
+    pub fn load() -> Result<Self, Error> {
+        let profiles =
+            serde_json::from_str(&read_to_string(PROFILES_FILE).unwrap_or("{}".to_string()))?;
+
+        Ok(profiles)
+    }
+
+    pub fn save(&self) -> Result<(), Error> {
+        let data = serde_json::to_string(self)?;
+        write(PROFILES_FILE, data)?;
+
+        Ok(())
+    }
+
+    pub fn join_guild(&mut self, guild_id: u64, user_id: u64) {
+        let users = self.guilds.entry(guild_id).or_default();
+        if !users.contains(&user_id) {
+            users.push(user_id);
+        }
+    }
+
+    /// The user's profile, only if they have shared it with groups
+    pub fn shared(&self, user_id: u64) -> Option<&Profile> {
+        self.users.get(&user_id).filter(|p| p.share_in_groups)
+    }
+
+    /// Users who have used `/profiili` on the guild and shared their profile
+    /// with groups
+    pub fn guild_users(&self, guild_id: u64) -> Vec<u64> {
+        self.guilds
+            .get(&guild_id)
+            .map(|users| {
+                users
+                    .iter()
+                    .copied()
+                    .filter(|u| self.shared(*u).is_some())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn remove(&mut self, user_id: u64) {
+        self.users.remove(&user_id);
+
+        for users in self.guilds.values_mut() {
+            users.retain(|u| *u != user_id);
+        }
+        self.guilds.retain(|_, users| !users.is_empty());
+    }
+}
+
+#[test]
+fn profile_conflicts() {
+    use crate::menu::Allergens;
+    use crate::menu::Menu;
+    use crate::types::day::DailyMenu;
+
+    let daily = std::fs::read_to_string("tests/2025-09-02.json").expect("no file");
+    let menu: DailyMenu = serde_json::from_str(&daily).expect("unable to parse json");
+    let menu: Menu = menu.into();
+    let dish = |title: &str| {
+        menu.dishes
+            .iter()
+            .find(|d| d.title_fi == title)
+            .expect("no dish")
+    };
+
+    let tuna = dish("Tonnikalapatonki");
+    let smoothie = dish("Persikka-ananassmoothie");
+
+    assert!(Profile::default().conflicts(tuna).is_empty());
+    assert!(Profile::default().is_empty());
+
+    let profile = Profile {
+        diet: DietFilter {
+            milk_free: true,
+            ..Default::default()
+        },
+        allergens: vec![AllergenKind::Gluten, AllergenKind::Mustard],
+        ingredients: vec!["ananas".to_string(), "tonnikala".to_string()],
+        hide_conflicts: false,
+        share_in_groups: false,
+    };
+    assert!(!profile.is_empty());
+    assert!(
+        Profile {
+            hide_conflicts: true,
+            share_in_groups: true,
+            ..Default::default()
+        }
+        .is_empty()
+    );
+
+    assert_eq!(
+        profile.conflicts(tuna),
+        vec![
+            Conflict::Allergen("Vehnä".to_string()),
+            Conflict::Allergen("Sinappi".to_string()),
+            Conflict::Ingredient("tonnikala".to_string()),
+        ]
+    );
+
+    let conflicts = profile.conflicts(smoothie);
+    assert_eq!(
+        describe_conflicts(&conflicts, Locale::Fi),
+        "Maidoton, ananas"
+    );
+    assert!(!conflicts[0].is_warning());
+    assert!(conflicts[1].is_warning());
+
+    // allergens the caterer left out of the list are found in the ingredients
+    let mut lasagne = dish("Jauhelihalasagnettea").clone();
+    lasagne.allergens = Allergens::default();
+    let avoid = Profile {
+        allergens: vec![AllergenKind::Milk, AllergenKind::Gluten],
+        ..Default::default()
+    };
+    let kinds = avoid
+        .conflicts(&lasagne)
+        .iter()
+        .map(|c| match c {
+            Conflict::Allergen(word) => AllergenKind::parse(word),
+            _ => AllergenKind::Unknown,
+        })
+        .collect::<Vec<AllergenKind>>();
+    assert_eq!(kinds.len(), 2);
+    assert!(kinds.contains(&AllergenKind::Milk) && kinds.contains(&AllergenKind::Gluten));
+
+    // but aren't repeated when the list has them
+    assert_eq!(
+        avoid.conflicts(dish("Persikka-ananassmoothie")),
+        vec![Conflict::Allergen("Maito".to_string())]
+    );
+}
+
+#[test]
+fn group_fits() {
+    use crate::menu::Menu;
+    use crate::types::day::DailyMenu;
+
+    let daily = std::fs::read_to_string("tests/2025-09-02.json").expect("no file");
+    let menu: DailyMenu = serde_json::from_str(&daily).expect("unable to parse json");
+    let menu: Menu = menu.into();
+
+    let vegan = Profile {
+        diet: DietFilter {
+            vegan: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let fish = Profile {
+        allergens: vec![AllergenKind::Fish],
+        ..Default::default()
+    };
+
+    let fits = group_fit(
+        &menu.dishes,
+        &[(1, fish), (2, vegan), (3, Profile::default())],
+    );
+    assert_eq!(fits.len(), menu.dishes.len());
+
+    let fit = |title: &str| {
+        fits.iter()
+            .find(|f| f.dish.title_fi == title)
+            .expect("no dish")
+    };
+
+    // only the ones who can't eat it are listed
+    let tuna = fit("Tonnikalapatonki");
+    assert_eq!(
+        tuna.conflicts
+            .iter()
+            .map(|(id, _)| *id)
+            .collect::<Vec<u64>>(),
+        vec![1, 2]
+    );
+    assert_eq!(
+        tuna.conflicts[0].1,
+        vec![Conflict::Allergen("Kala".to_string())]
+    );
+
+    let lasagne = fit("Jauhelihalasagnettea");
+    assert_eq!(
+        lasagne
+            .conflicts
+            .iter()
+            .map(|(id, _)| *id)
+            .collect::<Vec<u64>>(),
+        vec![2]
+    );
+
+    assert!(
+        group_fit(&menu.dishes, &[])
+            .iter()
+            .all(|f| f.conflicts.is_empty())
+    );
+}
+
+#[test]
+fn guild_users() {
+    let shared = Profile {
+        share_in_groups: true,
+        ..Default::default()
+    };
+
+    let mut profiles = Profiles::default();
+    profiles.users.insert(1, shared.clone());
+    profiles.users.insert(2, shared);
+    profiles.users.insert(4, Profile::default());
+
+    profiles.join_guild(10, 1);
+    profiles.join_guild(10, 1);
+    profiles.join_guild(10, 3);
+    profiles.join_guild(10, 4);
+    profiles.join_guild(20, 2);
+
+    // users without a profile or who haven't shared it are left out
+    assert_eq!(profiles.guild_users(10), vec![1]);
+    assert!(profiles.shared(4).is_none());
+    assert!(profiles.shared(1).is_some());
+    assert_eq!(profiles.guild_users(20), vec![2]);
+    assert!(profiles.guild_users(30).is_empty());
+
+    profiles.remove(2);
+    assert!(profiles.guild_users(20).is_empty());
+    assert!(!profiles.guilds.contains_key(&20));
+}
@@ -0,0 +1,244 @@
+use crate::Error;
+use crate::menu::Allergens;
+use crate::menu::Category;
+use crate::menu::DietCode;
+use crate::menu::DietFlags;
+use crate::menu::Dish;
+use crate::menu::Menu;
+use crate::menu::Price;
+use crate::menu::Recipe;
+use crate::menu::Week;
+use crate::menu::WeekDay;
+use crate::provider::BoxFuture;
+use crate::provider::MenuProvider;
+use chrono::Local;
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+/// Compass Group's menu api, restaurants are identified by their cost center
+pub struct Compass;
+
+const API: &str = "https://www.compass-group.fi/menuapi";
+
+const RESTAURANTS: &str = "https://www.compass-group.fi/ravintolat-ja-ruokalistat";
+
+impl MenuProvider for Compass {
+    fn fetch_day<'a>(&'a self, id: &'a str, day: &'a str) -> BoxFuture<'a, Result<Menu, Error>> {
+        Box::pin(async move {
+            let url = format!("{API}/day-menus?costCenter={id}&date={day}&language=fi");
+
+            let menu = reqwest::get(url).await?.json::<DayMenu>().await?;
+
+            Ok(menu.into_menu(id, None))
+        })
+    }
+
+    fn fetch_week<'a>(&'a self, id: &'a str) -> BoxFuture<'a, Result<Week, Error>> {
+        Box::pin(async move {
+            let day = Local::now().date_naive().format("%Y-%m-%d");
+            let url = format!("{API}/week-menus?costCenter={id}&date={day}&language=fi");
+
+            let menu = reqwest::get(url).await?.json::<WeekMenu>().await?;
+
+            menu.into_week(id)
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WeekMenu {
+    customer_name: Option<String>,
+    #[serde(default)]
+    menus: Vec<DayMenu>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DayMenu {
+    customer_name: Option<String>,
+    date: String,
+    day_of_week: Option<String>,
+    #[serde(default)]
+    menu_packages: Vec<MenuPackage>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MenuPackage {
+    sort_order: Option<i64>,
+    name: Option<String>,
+    price: Option<String>,
+    #[serde(default)]
+    meals: Vec<Meal>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Meal {
+    name: String,
+    #[serde(default)]
+    diets: Vec<String>,
+}
+
+impl DayMenu {
+    /// `date` looks like `2025-09-01T00:00:00+00:00`
+    fn date(&self) -> Result<NaiveDate, Error> {
+        let date = self.date.get(0..10).ok_or("invalid date in json")?;
+
+        Ok(NaiveDate::parse_from_str(date, "%Y-%m-%d")?)
+    }
+
+    fn into_menu(self, id: &str, customer_name: Option<&str>) -> Menu {
+        let title = self
+            .customer_name
+            .as_deref()
+            .or(customer_name)
+            .map(str::to_string)
+            .unwrap_or(format!("Compass Group {id}"));
+
+        Menu {
+            title,
+            url: RESTAURANTS.to_string(),
+            generated_timestamp: 0,
+            restaurant_mashie_id: None,
+            dishes: self
+                .menu_packages
+                .into_iter()
+                .enumerate()
+                .map(|(i, p)| p.into_dish(i))
+                .collect(),
+        }
+    }
+}
+
+impl MenuPackage {
+    fn into_dish(self, index: usize) -> Dish {
+        // the position gets its own prefix so it can't be mistaken for another
+        // package's sort order
+        let id = match self.sort_order {
+            Some(order) => order.to_string(),
+            None => format!("i{index}"),
+        };
+
+        let title = self
+            .meals
+            .iter()
+            .map(|m| m.name.as_str())
+            .collect::<Vec<&str>>()
+            .join(", ");
+
+        // a package is only as suitable as its least suitable meal
+        let meal_codes = self
+            .meals
+            .iter()
+            .map(|m| m.diets.iter().map(|d| DietCode::parse(d)).collect())
+            .collect::<Vec<Vec<DietCode>>>();
+        let diet_codes: Vec<DietCode> = meal_codes
+            .first()
+            .map(|first| {
+                first
+                    .iter()
+                    .filter(|c| meal_codes.iter().all(|m| m.contains(c)))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Dish {
+            id,
+            title_fi: title,
+            title_en: None,
+            category: Category::parse(self.name.as_deref()),
+            price: Price::parse(self.price.as_deref()),
+            diet: DietFlags::from(diet_codes.as_slice()),
+            diet_codes,
+            allergens: Allergens::default(),
+            recipes: self
+                .meals
+                .into_iter()
+                .map(|m| Recipe {
+                    name: m.name,
+                    ingredients: Vec::new(),
+                    nutrition: None,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl WeekMenu {
+    fn into_week(self, id: &str) -> Result<Week, Error> {
+        let customer_name = self.customer_name;
+
+        let days = self
+            .menus
+            .into_iter()
+            .map(|d| {
+                Ok(WeekDay {
+                    date: d.date()?,
+                    name: d.day_of_week.clone().unwrap_or_default(),
+                    menu: d.into_menu(id, customer_name.as_deref()),
+                })
+            })
+            .collect::<Result<Vec<WeekDay>, Error>>()?;
+
+        let start = days.first().ok_or("no days in week")?.date;
+        let end = days.last().ok_or("no days in week")?.date;
+
+        Ok(Week { start, end, days })
+    }
+}
+
+#[test]
+fn compass_daily_menu() {
+    let daily = std::fs::read_to_string("tests/compass_daily.json").expect("no file");
+    let menu: DayMenu = serde_json::from_str(&daily).expect("unable to parse json");
+
+    let menu = menu.into_menu("3087", None);
+    assert_eq!(menu.title, "Ravintola Snellmania");
+    assert_eq!(menu.dishes.len(), 2);
+
+    let soup = menu.dish("1").expect("missing dish");
+    assert_eq!(soup.title_fi, "Kukkakaalisosekeittoa");
+    assert!(soup.diet.gluten_free);
+    assert!(soup.diet.low_lactose);
+    assert!(!soup.diet.milk_free);
+
+    let dessert = menu.dish("2").expect("missing dish");
+    assert_eq!(dessert.price, Price::Unknown);
+
+    let unsorted: MenuPackage =
+        serde_json::from_str(r#"{"meals": [{"name": "Puuro"}]}"#).expect("unable to parse json");
+    assert_eq!(unsorted.into_dish(1).id, "i1");
+}
+
+#[test]
+fn compass_weekly_menu() {
+    let weekly = std::fs::read_to_string("tests/compass_weekly.json").expect("no file");
+    let menu: WeekMenu = serde_json::from_str(&weekly).expect("unable to parse json");
+
+    let week = menu.into_week("3087").expect("unable to convert week");
+    assert_eq!(
+        week.start,
+        NaiveDate::from_ymd_opt(2025, 9, 1).expect("invalid date")
+    );
+    assert_eq!(
+        week.end,
+        NaiveDate::from_ymd_opt(2025, 9, 7).expect("invalid date")
+    );
+    assert_eq!(week.days.len(), 3);
+    assert_eq!(week.days[0].name, "Maanantai");
+
+    let vegan = &week.days[0].menu.dishes[0];
+    assert_eq!(vegan.title_fi, "Linssi-kasviskastike, Tummaa riisiä");
+    assert!(vegan.diet.vegan);
+    // only the rice is marked with `*`, and it isn't the better choice heart anyway
+    assert!(!vegan.diet_codes.contains(&crate::menu::DietCode::Recommended));
+    assert!(!vegan.diet.better_choice);
+
+    let meat = &week.days[0].menu.dishes[1];
+    assert!(!meat.diet.vegan);
+    assert!(meat.diet.gluten_free);
+    assert!(!meat.diet.lactose_free);
+}
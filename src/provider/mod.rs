@@ -0,0 +1,158 @@
+use crate::Error;
+use crate::menu::Menu;
+use crate::menu::Week;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
+use std::fmt::Display;
+use std::future::Future;
+use std::pin::Pin;
+use std::str::FromStr;
+
+pub mod compass;
+pub mod sodexo;
+
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// A caterer whose menus can be fetched and converted into a [`Menu`]
+pub trait MenuProvider: Send + Sync {
+    /// `day` is formatted as `YYYY-MM-DD`
+    fn fetch_day<'a>(&'a self, id: &'a str, day: &'a str) -> BoxFuture<'a, Result<Menu, Error>>;
+
+    /// The current week
+    fn fetch_week<'a>(&'a self, id: &'a str) -> BoxFuture<'a, Result<Week, Error>>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ProviderKind {
+    Sodexo,
+    Compass,
+}
+
+impl ProviderKind {
+    pub fn provider(self) -> &'static dyn MenuProvider {
+        match self {
+            ProviderKind::Sodexo => &sodexo::Sodexo,
+            ProviderKind::Compass => &compass::Compass,
+        }
+    }
+
+    fn prefix(self) -> &'static str {
+        match self {
+            ProviderKind::Sodexo => "sodexo",
+            ProviderKind::Compass => "compass",
+        }
+    }
+}
+
+/// Centria campus, used when neither the guild nor the channel has chosen a restaurant
+pub const DEFAULT_RESTAURANT: &str = "129";
+
+/// A restaurant of some caterer, written as `compass:3087`. Plain numbers are
+/// Sodexo restaurants, which is also what they were before other caterers existed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RestaurantId {
+    pub provider: ProviderKind,
+    pub id: String,
+}
+
+impl RestaurantId {
+    pub async fn fetch_day(&self, day: &str) -> Result<Menu, Error> {
+        self.provider.provider().fetch_day(&self.id, day).await
+    }
+
+    pub async fn fetch_week(&self) -> Result<Week, Error> {
+        self.provider.provider().fetch_week(&self.id).await
+    }
+}
+
+impl Default for RestaurantId {
+    fn default() -> Self {
+        RestaurantId {
+            provider: ProviderKind::Sodexo,
+            id: DEFAULT_RESTAURANT.to_string(),
+        }
+    }
+}
+
+impl Display for RestaurantId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.provider {
+            ProviderKind::Sodexo => write!(f, "{}", self.id),
+            p => write!(f, "{}:{}", p.prefix(), self.id),
+        }
+    }
+}
+
+impl FromStr for RestaurantId {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (provider, id) = match s.trim().split_once(':') {
+            Some(("sodexo", id)) => (ProviderKind::Sodexo, id),
+            Some(("compass", id)) => (ProviderKind::Compass, id),
+            Some((p, _)) => return Err(format!("unknown caterer `{p}`").into()),
+            None => (ProviderKind::Sodexo, s.trim()),
+        };
+
+        // the id ends up in urls and in button ids, where `_` separates fields
+        if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return Err(format!("invalid restaurant `{s}`").into());
+        }
+
+        Ok(RestaurantId {
+            provider,
+            id: id.to_string(),
+        })
+    }
+}
+
+impl Serialize for RestaurantId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for RestaurantId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // stored settings from before other caterers have plain numbers
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Helper {
+            Number(u64),
+            String(String),
+        }
+
+        match Helper::deserialize(deserializer)? {
+            Helper::Number(n) => Ok(RestaurantId {
+                provider: ProviderKind::Sodexo,
+                id: n.to_string(),
+            }),
+            Helper::String(s) => s.parse().map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+#[test]
+fn restaurant_id() {
+    let sodexo: RestaurantId = "129".parse().expect("unable to parse id");
+    assert_eq!(sodexo, RestaurantId::default());
+    assert_eq!(sodexo.to_string(), "129");
+    assert_eq!("sodexo:129".parse::<RestaurantId>().ok(), Some(sodexo));
+
+    let compass: RestaurantId = "compass:3087".parse().expect("unable to parse id");
+    assert_eq!(compass.provider, ProviderKind::Compass);
+    assert_eq!(compass.to_string(), "compass:3087");
+
+    assert!("unicafe:1".parse::<RestaurantId>().is_err());
+    assert!("".parse::<RestaurantId>().is_err());
+    assert!("1_2".parse::<RestaurantId>().is_err());
+
+    let ids: Vec<RestaurantId> =
+        serde_json::from_str(r#"[129, "compass:3087"]"#).expect("unable to parse json");
+    assert_eq!(
+        serde_json::to_string(&ids).expect("unable to serialize"),
+        r#"["129","compass:3087"]"#
+    );
+}
@@ -0,0 +1,217 @@
+use crate::Error;
+use crate::dates::date_in_week;
+use crate::dates::parse_timeperiod;
+use crate::dates::parse_weekday;
+use crate::menu::Allergens;
+use crate::menu::Category;
+use crate::menu::DietFlags;
+use crate::menu::Dish;
+use crate::menu::Menu;
+use crate::menu::Nutrition;
+use crate::menu::Price;
+use crate::menu::Recipe;
+use crate::menu::Week;
+use crate::menu::WeekDay;
+use crate::menu::ingredient::parse_ingredients;
+use crate::provider::BoxFuture;
+use crate::provider::MenuProvider;
+use crate::types::common::Course;
+use crate::types::common::DietInfo;
+use crate::types::common::Meta;
+use crate::types::day::DailyMenu;
+use crate::types::week::WeeklyMenu;
+use chrono::Local;
+use chrono::NaiveDate;
+
+pub struct Sodexo;
+
+impl MenuProvider for Sodexo {
+    fn fetch_day<'a>(&'a self, id: &'a str, day: &'a str) -> BoxFuture<'a, Result<Menu, Error>> {
+        Box::pin(async move {
+            let url = format!("https://sodexo.fi/ruokalistat/output/daily_json/{id}/{day}");
+
+            let menu = reqwest::get(url).await?.json::<DailyMenu>().await?;
+
+            Ok(menu.into())
+        })
+    }
+
+    fn fetch_week<'a>(&'a self, id: &'a str) -> BoxFuture<'a, Result<Week, Error>> {
+        Box::pin(async move {
+            let url = format!("https://sodexo.fi/ruokalistat/output/weekly_json/{id}");
+
+            let menu = reqwest::get(url).await?.json::<WeeklyMenu>().await?;
+
+            week(menu, Local::now().date_naive())
+        })
+    }
+}
+
+/// Sodexo sends an empty string for missing english titles
+fn non_empty(s: Option<String>) -> Option<String> {
+    s.filter(|s| !s.trim().is_empty())
+}
+
+fn menu(meta: Meta, courses: Vec<(String, Course)>) -> Menu {
+    Menu {
+        title: meta.ref_title,
+        url: meta.ref_url,
+        generated_timestamp: meta.generated_timestamp,
+        restaurant_mashie_id: Some(meta.restaurant_mashie_id),
+        dishes: courses.into_iter().map(dish).collect(),
+    }
+}
+
+fn dish((id, course): (String, Course)) -> Dish {
+    let (recipes, course_hide_all) = course
+        .recipes
+        .map(|r| (r.recipes, r.hide_all.map(|h| h.diet_info)))
+        .unwrap_or_default();
+
+    let recipes = recipes
+        .into_iter()
+        .map(|r| Recipe {
+            name: r.name,
+            ingredients: parse_ingredients(&r.ingredients.into_option().unwrap_or_default()),
+            nutrition: Some(Nutrition::parse(&r.nutrients)).filter(|n| !n.is_empty()),
+        })
+        .collect();
+
+    let info = course.additional_diet_info;
+    if !info.food_info.unknown.is_empty() {
+        println!(
+            "Course {id} has unrecognized diet icons {}",
+            info.food_info.unknown.join(", ")
+        );
+    }
+    let diet_info = diet_info(&id, &course.diet_info, &course.properties, course_hide_all);
+
+    Dish {
+        id,
+        title_fi: non_empty(course.title_fi).unwrap_or("N/A".to_string()),
+        title_en: non_empty(course.title_en),
+        category: Category::parse(course.category.as_deref()),
+        price: Price::parse(course.price.as_deref()),
+        diet: DietFlags::from((&diet_info, &info.food_info)),
+        diet_codes: diet_info.codes,
+        allergens: Allergens::parse(info.allergens.as_deref()),
+        recipes,
+    }
+}
+
+/// `dietcodes` is the one to trust, `properties` repeats it and is only used when
+/// `dietcodes` is missing. `hideAll` doesn't always agree with either, a dish
+/// marked `L` can have `M` there, so it's never trusted for suitability and any
+/// disagreement is only logged.
+fn diet_info(
+    id: &str,
+    codes: &DietInfo,
+    properties: &DietInfo,
+    hide_all: Option<DietInfo>,
+) -> DietInfo {
+    let codes = match codes.codes.is_empty() {
+        true => properties.clone(),
+        false => codes.clone(),
+    };
+
+    let missing = properties
+        .codes
+        .iter()
+        .chain(hide_all.iter().flat_map(|h| &h.codes))
+        .filter(|c| !codes.codes.contains(c))
+        .map(ToString::to_string)
+        .collect::<Vec<String>>();
+    if !missing.is_empty() {
+        println!(
+            "Course {id} diet codes don't include {} from properties or hideAll",
+            missing.join(", ")
+        );
+    }
+
+    codes
+}
+
+impl From<DailyMenu> for Menu {
+    fn from(val: DailyMenu) -> Self {
+        menu(val.meta, val.courses)
+    }
+}
+
+/// The weekly menu only has a `timeperiod` like `25.8. - 31.8.` and weekday
+/// names, `today` is needed to know the year
+pub(crate) fn week(val: WeeklyMenu, today: NaiveDate) -> Result<Week, Error> {
+    let (start, end) = parse_timeperiod(&val.timeperiod, today)?;
+    let meta = val.meta;
+
+    let days = val
+        .mealdates
+        .into_iter()
+        .map(|d| {
+            let weekday = parse_weekday(&d.date).ok_or(format!("unknown weekday `{}`", d.date))?;
+
+            Ok(WeekDay {
+                date: date_in_week(start, weekday),
+                name: d.date,
+                menu: menu(meta.clone(), d.courses),
+            })
+        })
+        .collect::<Result<Vec<WeekDay>, Error>>()?;
+
+    Ok(Week { start, end, days })
+}
+
+#[test]
+fn convert_daily_menu() {
+    let daily = std::fs::read_to_string("tests/2025-09-02.json").expect("no file");
+    let menu: DailyMenu = serde_json::from_str(&daily).expect("unable to parse json");
+
+    let menu: Menu = menu.into();
+    assert_eq!(menu.title, "Campusravintola");
+
+    let dish = menu.dish("1").expect("missing dish");
+    assert_eq!(dish.title_fi, "Falafel-papupyttipannua");
+    assert_eq!(dish.title_en, None);
+    assert_eq!(dish.price, Price::Unknown);
+    assert_eq!(dish.allergens.0.len(), 1);
+
+    // the fixture only lists herneet, hiivauute and sitrukset outside of the known allergens
+    let unknown = menu
+        .dishes
+        .iter()
+        .flat_map(|d| d.allergens.iter())
+        .filter(|a| a.kind == crate::menu::allergen::AllergenKind::Unknown)
+        .map(|a| a.name.as_str())
+        .collect::<std::collections::BTreeSet<&str>>();
+    assert!(
+        unknown
+            .iter()
+            .all(|a| ["Herneet", "Hiivauute", "Sitrukset"].contains(a))
+    );
+    assert_eq!(dish.recipes.len(), 1);
+    assert!(dish.recipes[0].nutrition.is_some());
+}
+
+#[test]
+fn convert_weekly_menu() {
+    let weekly = std::fs::read_to_string("tests/weekly.json").expect("no file");
+    let menu: WeeklyMenu = serde_json::from_str(&weekly).expect("unable to parse json");
+
+    let today = NaiveDate::from_ymd_opt(2025, 8, 27).expect("invalid date");
+    let week = week(menu, today).expect("unable to convert week");
+    assert_eq!(
+        week.start,
+        NaiveDate::from_ymd_opt(2025, 8, 25).expect("invalid date")
+    );
+    assert_eq!(
+        week.end,
+        NaiveDate::from_ymd_opt(2025, 8, 31).expect("invalid date")
+    );
+
+    assert_eq!(week.days[0].name, "Maanantai");
+    assert_eq!(week.days[0].date, week.start);
+    assert!(!week.days[0].menu.dishes.is_empty());
+
+    for (i, day) in week.days.iter().enumerate() {
+        assert_eq!(day.date, week.start + chrono::Days::new(i as u64));
+    }
+}
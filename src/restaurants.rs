@@ -0,0 +1,258 @@
+use crate::Context;
+use crate::Error;
+use crate::menu::Menu;
+use crate::provider::ProviderKind;
+use crate::provider::RestaurantId;
+use chrono::Local;
+use serde::Deserialize;
+use serde::Serialize;
+use serenity::all::AutocompleteChoice;
+use std::env;
+use std::fs::read_to_string;
+use std::fs::write;
+
+/// Restaurants shipped with the bot, the on disk directory is layered on top of these
+const BUNDLED: &str = include_str!("../data/restaurants.json");
+
+const DIRECTORY_FILE: &str = "restaurants.json";
+
+/// Sodexo doesn't publish a list of its restaurants, so new ones are discovered
+/// by trying every id up to this one
+const DEFAULT_DISCOVERY_MAX: u64 = 400;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Restaurant {
+    pub id: RestaurantId,
+    pub name: String,
+    #[serde(default)]
+    pub city: String,
+    pub ref_url: String,
+    #[serde(default)]
+    pub restaurant_mashie_id: Option<String>,
+}
+
+impl Restaurant {
+    pub fn from_menu(id: RestaurantId, menu: &Menu) -> Self {
+        Restaurant {
+            id,
+            name: menu.title.clone(),
+            city: city_from_url(&menu.url).unwrap_or_default(),
+            ref_url: menu.url.clone(),
+            restaurant_mashie_id: menu.restaurant_mashie_id.clone(),
+        }
+    }
+
+    fn matches(&self, query: &str) -> bool {
+        let query = query.to_lowercase();
+
+        self.id.to_string().starts_with(&query)
+            || self.name.to_lowercase().contains(&query)
+            || self.city.to_lowercase().contains(&query)
+            || self.ref_url.to_lowercase().contains(&query)
+    }
+}
+
+impl std::fmt::Display for Restaurant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}, {} ({})", self.name, self.city, self.id)
+    }
+}
+
+/// The ref_url looks like `https://www.sodexo.fi/ravintolat/{city}/{restaurant}`
+fn city_from_url(url: &str) -> Option<String> {
+    let mut segments = url.split('/').skip_while(|s| *s != "ravintolat").skip(1);
+    let city = segments.next().filter(|s| !s.is_empty())?;
+
+    let mut chars = city.chars();
+    let first = chars.next()?;
+
+    Some(first.to_uppercase().chain(chars).collect())
+}
+
+#[derive(Debug, Default)]
+pub struct Directory {
+    restaurants: Vec<Restaurant>,
+}
+
+impl Directory {
+    /// Only the restaurants shipped with the bot
+    fn bundled() -> Result<Self, Error> {
+        Ok(Directory {
+            restaurants: serde_json::from_str(BUNDLED)?,
+        })
+    }
+
+    pub fn load() -> Result<Self, Error> {
+        let mut directory = Self::bundled()?;
+
+        let stored: Vec<Restaurant> =
+            serde_json::from_str(&read_to_string(DIRECTORY_FILE).unwrap_or("[]".to_string()))?;
+
+        for r in stored {
+            directory.insert(r);
+        }
+
+        Ok(directory)
+    }
+
+    pub fn save(&self) -> Result<(), Error> {
+        let data = serde_json::to_string(&self.restaurants)?;
+        write(DIRECTORY_FILE, data)?;
+
+        Ok(())
+    }
+
+    pub fn get(&self, id: &RestaurantId) -> Option<&Restaurant> {
+        self.restaurants.iter().find(|r| r.id == *id)
+    }
+
+    /// Adds the restaurant or replaces the existing one with the same id
+    pub fn insert(&mut self, restaurant: Restaurant) {
+        match self.restaurants.iter_mut().find(|r| r.id == restaurant.id) {
+            Some(r) => *r = restaurant,
+            None => {
+                self.restaurants.push(restaurant);
+                self.restaurants.sort_by(|a, b| a.id.cmp(&b.id));
+            }
+        }
+    }
+
+    pub fn search(&self, query: &str) -> Vec<&Restaurant> {
+        self.restaurants
+            .iter()
+            .filter(|r| r.matches(query.trim()))
+            .collect()
+    }
+
+    pub fn ids(&self) -> Vec<RestaurantId> {
+        self.restaurants.iter().map(|r| r.id.clone()).collect()
+    }
+}
+
+/// Fetches today's menu only for the metadata
+pub async fn fetch_restaurant(id: &RestaurantId) -> Result<Restaurant, Error> {
+    let day = Local::now().date_naive().format("%Y-%m-%d").to_string();
+    let menu = id.fetch_day(&day).await?;
+
+    Ok(Restaurant::from_menu(id.clone(), &menu))
+}
+
+/// The Sodexo ids up to `max` that aren't in the directory yet
+fn undiscovered(known: &[RestaurantId], max: u64) -> Vec<RestaurantId> {
+    (1..=max)
+        .map(|n| RestaurantId {
+            provider: ProviderKind::Sodexo,
+            id: n.to_string(),
+        })
+        .filter(|id| !known.contains(id))
+        .collect()
+}
+
+#[derive(Debug, Default)]
+pub struct Refreshed {
+    /// Known restaurants whose metadata couldn't be fetched
+    pub failed: Vec<RestaurantId>,
+    /// Restaurants that weren't in the directory before
+    pub discovered: Vec<RestaurantId>,
+}
+
+/// Re-fetches the metadata of every known restaurant and tries the Sodexo ids
+/// up to `$SODEXO_DISCOVERY_MAX` that aren't known yet. An unknown id that
+/// fails or has no name isn't a restaurant, so only known ones count as failed.
+pub async fn refresh(ctx: Context<'_>) -> Result<Refreshed, Error> {
+    let max = match env::var("SODEXO_DISCOVERY_MAX") {
+        Ok(max) => max.parse()?,
+        Err(_) => DEFAULT_DISCOVERY_MAX,
+    };
+    let ids = ctx.data().restaurants.lock().await.ids();
+
+    let mut refreshed = Refreshed::default();
+    let mut fetched = Vec::new();
+
+    for id in &ids {
+        match fetch_restaurant(id).await {
+            Ok(r) => fetched.push(r),
+            Err(e) => {
+                println!("unable to refresh restaurant {id}: {e:?}");
+                refreshed.failed.push(id.clone());
+            }
+        }
+    }
+
+    for id in undiscovered(&ids, max) {
+        if let Ok(r) = fetch_restaurant(&id).await
+            && !r.name.trim().is_empty()
+        {
+            println!("discovered restaurant {r}");
+            refreshed.discovered.push(id);
+            fetched.push(r);
+        }
+    }
+
+    let mut directory = ctx.data().restaurants.lock().await;
+    for r in fetched {
+        directory.insert(r);
+    }
+    directory.save()?;
+
+    Ok(refreshed)
+}
+
+/// Autocomplete for any command argument that takes a restaurant id
+pub async fn autocomplete_restaurant(ctx: Context<'_>, partial: &str) -> Vec<AutocompleteChoice> {
+    ctx.data()
+        .restaurants
+        .lock()
+        .await
+        .search(partial)
+        .into_iter()
+        // discord only shows 25 choices
+        .take(25)
+        .map(|r| AutocompleteChoice::new(r.to_string(), r.id.to_string()))
+        .collect()
+}
+
+#[test]
+fn bundled_directory() {
+    let directory = Directory::bundled().expect("unable to parse bundled directory");
+
+    let centria = directory
+        .get(&RestaurantId::default())
+        .expect("default restaurant missing");
+    assert_eq!(centria.city, "Kokkola");
+
+    assert_eq!(directory.search("kokkola").len(), 1);
+    assert_eq!(directory.search("CENTRIA").len(), 1);
+    assert_eq!(directory.search("12").len(), 1);
+    assert!(directory.search("helsinki").is_empty());
+}
+
+#[test]
+fn undiscovered_ids() {
+    let known = vec![
+        RestaurantId::default(),
+        "compass:3".parse().expect("unable to parse id"),
+    ];
+
+    let ids = undiscovered(&known, 130);
+    assert_eq!(ids.len(), 129);
+    assert!(!ids.contains(&RestaurantId::default()));
+    assert!(ids.contains(&"3".parse().expect("unable to parse id")));
+    assert!(ids.iter().all(|id| id.provider == ProviderKind::Sodexo));
+}
+
+#[test]
+fn restaurant_from_menu() {
+    use crate::types::day::DailyMenu;
+
+    let daily = std::fs::read_to_string("tests/daily.json").expect("no file");
+    let menu: DailyMenu = serde_json::from_str(&daily).expect("unable to parse json");
+
+    let restaurant = Restaurant::from_menu(RestaurantId::default(), &menu.into());
+    assert_eq!(restaurant.name, "Campusravintola");
+    assert_eq!(restaurant.city, "Kokkola");
+    assert_eq!(
+        restaurant.restaurant_mashie_id.as_deref(),
+        Some("FI739646K")
+    );
+}
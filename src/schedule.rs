@@ -1,6 +1,6 @@
-use crate::list::fetch_day;
+use crate::cache::cached_day;
 use crate::list::fmt_day;
-use crate::{Context, Error};
+use crate::{Context, Data, Error};
 use chrono::Local;
 use chrono_tz::Europe::Helsinki;
 use poise::serenity_prelude::CreateMessage;
@@ -21,12 +21,15 @@ pub struct DataJob {
     pub uuid: Uuid,
     pub cron: Box<str>,
     pub channel_id: u64,
+    pub guild_id: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct StoredJob {
     pub cron: Box<str>,
     pub channel_id: u64,
+    #[serde(default)]
+    pub guild_id: Option<u64>,
 }
 
 impl From<&DataJob> for StoredJob {
@@ -34,25 +37,41 @@ impl From<&DataJob> for StoredJob {
         StoredJob {
             cron: val.cron.clone(),
             channel_id: val.channel_id,
+            guild_id: val.guild_id,
         }
     }
 }
 
 pub fn create_scheduled_day_post<S: ToString>(
     ctx: &poise::serenity_prelude::Context,
+    data: Data,
     cron: S,
     channel_id: ChannelId,
+    guild_id: Option<u64>,
 ) -> Result<JobLocked, JobSchedulerError> {
     let ctx = Arc::new(ctx.clone());
 
     Job::new_async_tz(cron, Helsinki, move |_uuid, _l| {
         let ctx = ctx.clone();
+        let data = data.clone();
         Box::pin(async move {
             let day = Local::now().date_naive().format("%Y-%m-%d").to_string();
 
-            match fetch_day(&day).await {
-                Ok(menu) => {
-                    let reply = fmt_day(&day, menu, None);
+            // resolved on every run, so changing the restaurant also affects existing jobs
+            let (restaurant, order, view) = {
+                let settings = data.settings.lock().await;
+                (
+                    settings.restaurant(guild_id, channel_id.get()),
+                    settings.course_order(guild_id),
+                    settings.view(guild_id, None, None),
+                )
+            };
+
+            match cached_day(&data, &restaurant, &day).await {
+                Ok(mut menu) => {
+                    menu.menu.sort_dishes(order);
+                    let note = menu.outdated_note(view.locale);
+                    let reply = fmt_day(&restaurant, &day, menu.menu, note.as_deref(), &view);
 
                     let m = reply.to_prefix(MessageReference::new(
                         MessageReferenceKind::Default,
@@ -66,7 +85,8 @@ pub fn create_scheduled_day_post<S: ToString>(
                     if let Err(e) = channel_id
                         .send_message(
                             &ctx.http,
-                            CreateMessage::default().content(format!("Error fetching menu {e:#?}")),
+                            CreateMessage::default()
+                                .content(format!("{} {e:#?}", view.locale.strings().fetch_failed)),
                         )
                         .await
                     {
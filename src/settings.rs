@@ -0,0 +1,238 @@
+use crate::Context;
+use crate::Data;
+use crate::Error;
+use crate::locale::Locale;
+use crate::menu::CourseOrder;
+use crate::menu::DietFilter;
+use crate::menu::PriceTier;
+use crate::profile::Profile;
+use crate::provider::RestaurantId;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs::read_to_string;
+use std::fs::write;
+
+const SETTINGS_FILE: &str = "settings.json";
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct GuildSettings {
+    pub restaurant: Option<RestaurantId>,
+    pub course_order: Option<CourseOrder>,
+    pub price_tier: Option<PriceTier>,
+    /// Used when the reader's own language isn't known, like in scheduled posts
+    pub locale: Option<Locale>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ChannelSettings {
+    pub restaurant: Option<RestaurantId>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct UserSettings {
+    pub price_tier: Option<PriceTier>,
+}
+
+/// How a menu is shown to whoever is reading it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct View {
+    pub locale: Locale,
+    pub tier: Option<PriceTier>,
+    pub filter: DietFilter,
+    /// The reader's own profile, empty for posts not made for anyone in particular
+    pub profile: Profile,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Settings {
+    #[serde(default)]
+    pub guilds: HashMap<u64, GuildSettings>,
+    #[serde(default)]
+    pub channels: HashMap<u64, ChannelSettings>,
+    #[serde(default)]
+    pub users: HashMap<u64, UserSettings>,
+}
+
+impl Settings {
+    pub fn load() -> Result<Self, Error> {
+        let settings =
+            serde_json::from_str(&read_to_string(SETTINGS_FILE).unwrap_or("{}".to_string()))?;
+
+        Ok(settings)
+    }
+
+    pub fn save(&self) -> Result<(), Error> {
+        let data = serde_json::to_string(self)?;
+        write(SETTINGS_FILE, data)?;
+
+        Ok(())
+    }
+
+    /// Channel override first, then the guild default, then [`RestaurantId::default`]
+    pub fn restaurant(&self, guild_id: Option<u64>, channel_id: u64) -> RestaurantId {
+        self.channels
+            .get(&channel_id)
+            .and_then(|c| c.restaurant.clone())
+            .or_else(|| {
+                guild_id
+                    .and_then(|g| self.guilds.get(&g))
+                    .and_then(|g| g.restaurant.clone())
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn course_order(&self, guild_id: Option<u64>) -> CourseOrder {
+        guild_id
+            .and_then(|g| self.guilds.get(&g))
+            .and_then(|g| g.course_order)
+            .unwrap_or_default()
+    }
+
+    /// The user's own tier first, then the guild default, `None` shows every price
+    pub fn price_tier(&self, guild_id: Option<u64>, user_id: Option<u64>) -> Option<PriceTier> {
+        user_id
+            .and_then(|u| self.users.get(&u))
+            .and_then(|u| u.price_tier)
+            .or_else(|| {
+                guild_id
+                    .and_then(|g| self.guilds.get(&g))
+                    .and_then(|g| g.price_tier)
+            })
+    }
+
+    /// The user's Discord language if there's a translation for it, then the guild default
+    pub fn locale(&self, guild_id: Option<u64>, user_locale: Option<&str>) -> Locale {
+        user_locale
+            .and_then(Locale::from_discord)
+            .or_else(|| {
+                guild_id
+                    .and_then(|g| self.guilds.get(&g))
+                    .and_then(|g| g.locale)
+            })
+            .unwrap_or_default()
+    }
+
+    /// `user_id` and `user_locale` are `None` for posts not made for anyone in particular
+    pub fn view(
+        &self,
+        guild_id: Option<u64>,
+        user_id: Option<u64>,
+        user_locale: Option<&str>,
+    ) -> View {
+        View {
+            locale: self.locale(guild_id, user_locale),
+            tier: self.price_tier(guild_id, user_id),
+            filter: DietFilter::default(),
+            profile: Profile::default(),
+        }
+    }
+}
+
+/// Restaurant for the channel the command was invoked in
+pub async fn current_restaurant(ctx: Context<'_>) -> RestaurantId {
+    ctx.data()
+        .settings
+        .lock()
+        .await
+        .restaurant(ctx.guild_id().map(|g| g.get()), ctx.channel_id().get())
+}
+
+/// Course order of the guild the command was invoked in
+pub async fn current_course_order(ctx: Context<'_>) -> CourseOrder {
+    ctx.data()
+        .settings
+        .lock()
+        .await
+        .course_order(ctx.guild_id().map(|g| g.get()))
+}
+
+/// Price tier of the user who invoked the command
+pub async fn current_price_tier(ctx: Context<'_>) -> Option<PriceTier> {
+    ctx.data()
+        .settings
+        .lock()
+        .await
+        .price_tier(ctx.guild_id().map(|g| g.get()), Some(ctx.author().id.get()))
+}
+
+/// [`Settings::view`] for a user, with their profile
+pub async fn user_view(
+    data: &Data,
+    guild_id: Option<u64>,
+    user_id: u64,
+    user_locale: Option<&str>,
+) -> View {
+    let view = data
+        .settings
+        .lock()
+        .await
+        .view(guild_id, Some(user_id), user_locale);
+    let profile = data.profiles.lock().await.users.get(&user_id).cloned();
+
+    View {
+        profile: profile.unwrap_or_default(),
+        ..view
+    }
+}
+
+/// Language, price tier and profile of the user who invoked the command
+pub async fn current_view(ctx: Context<'_>) -> View {
+    user_view(
+        ctx.data(),
+        ctx.guild_id().map(|g| g.get()),
+        ctx.author().id.get(),
+        ctx.locale(),
+    )
+    .await
+}
+
+#[test]
+fn restaurant_resolution() {
+    let id = |s: &str| s.parse::<RestaurantId>().expect("unable to parse id");
+
+    let mut settings = Settings::default();
+    assert_eq!(settings.restaurant(Some(1), 2), RestaurantId::default());
+
+    settings.guilds.entry(1).or_default().restaurant = Some(id("10"));
+    assert_eq!(settings.restaurant(Some(1), 2), id("10"));
+    assert_eq!(settings.restaurant(None, 2), RestaurantId::default());
+
+    settings.channels.entry(2).or_default().restaurant = Some(id("compass:20"));
+    assert_eq!(settings.restaurant(Some(1), 2), id("compass:20"));
+    assert_eq!(settings.restaurant(Some(1), 3), id("10"));
+}
+
+#[test]
+fn price_tier_resolution() {
+    let mut settings = Settings::default();
+    assert_eq!(settings.price_tier(Some(1), Some(2)), None);
+
+    settings.guilds.entry(1).or_default().price_tier = Some(PriceTier::Staff);
+    assert_eq!(
+        settings.price_tier(Some(1), Some(2)),
+        Some(PriceTier::Staff)
+    );
+    assert_eq!(settings.price_tier(Some(1), None), Some(PriceTier::Staff));
+    assert_eq!(settings.price_tier(None, Some(2)), None);
+
+    settings.users.entry(2).or_default().price_tier = Some(PriceTier::Student);
+    assert_eq!(
+        settings.price_tier(Some(1), Some(2)),
+        Some(PriceTier::Student)
+    );
+    assert_eq!(settings.price_tier(None, Some(2)), Some(PriceTier::Student));
+}
+
+#[test]
+fn locale_resolution() {
+    let mut settings = Settings::default();
+    assert_eq!(settings.locale(Some(1), None), Locale::Fi);
+    assert_eq!(settings.locale(Some(1), Some("en-US")), Locale::En);
+
+    settings.guilds.entry(1).or_default().locale = Some(Locale::En);
+    assert_eq!(settings.locale(Some(1), None), Locale::En);
+    assert_eq!(settings.locale(Some(1), Some("sv-SE")), Locale::En);
+    assert_eq!(settings.locale(Some(1), Some("fi")), Locale::Fi);
+    assert_eq!(settings.locale(None, Some("sv-SE")), Locale::Fi);
+}
@@ -1,17 +1,34 @@
 use either::Either;
 use either::Either::Left;
 use either::Either::Right;
+use crate::menu::diet::DietCode;
+use crate::menu::diet::parse_diet_codes;
 use serde::Deserialize;
 use serde::Deserializer;
 use std::collections::HashMap;
 
+/// Orders entries by their numeric key, keys that aren't numbers go last
+pub fn sorted_by_key<T>(map: HashMap<String, T>) -> Vec<(String, T)> {
+    let mut entries: Vec<(String, T)> = map.into_iter().collect();
+    entries.sort_by(
+        |(a, _), (b, _)| match (a.parse::<u64>(), b.parse::<u64>()) {
+            (Ok(a), Ok(b)) => a.cmp(&b),
+            (Ok(_), Err(_)) => std::cmp::Ordering::Less,
+            (Err(_), Ok(_)) => std::cmp::Ordering::Greater,
+            (Err(_), Err(_)) => a.cmp(b),
+        },
+    );
+
+    entries
+}
+
 pub fn deserialize_as_vec<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
 where
     D: Deserializer<'de>,
     T: Deserialize<'de>,
 {
     let map: HashMap<String, T> = HashMap::deserialize(deserializer)?;
-    Ok(map.into_values().collect())
+    Ok(sorted_by_key(map).into_iter().map(|(_, v)| v).collect())
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -27,13 +44,14 @@ pub struct Course {
     pub title_fi: Option<String>,
     pub title_en: Option<String>,
     pub category: Option<String>,
-    pub meal_category: Option<String>,
     #[serde(rename(deserialize = "dietcodes"))]
     #[serde(deserialize_with = "deserialize_diet_info")]
     #[serde(default)]
     pub diet_info: DietInfo,
-    // Repeat of dietcodes
-    pub properties: Option<String>,
+    /// Repeat of dietcodes
+    #[serde(deserialize_with = "deserialize_diet_info")]
+    #[serde(default)]
+    pub properties: DietInfo,
     #[serde(rename(deserialize = "additionalDietInfo"))]
     #[serde(default)]
     pub additional_diet_info: AdditionalDietInfo,
@@ -61,108 +79,74 @@ pub struct FoodInfo {
     pub fi_meat: bool,
     pub eu_meat: bool,
     pub other_meat: bool,
+    /// Stems of icons missing from [`DIET_ICONS`]
+    pub unknown: Vec<String>,
 }
 
-fn deserialize_food_info<'de, D>(deserializer: D) -> Result<FoodInfo, D::Error>
-where
-    D: Deserializer<'de>,
-{
-    let mut food_info = FoodInfo::default();
-
-    let urls: Option<Vec<String>> = Option::deserialize(deserializer)?;
-
-    match urls {
-        Some(urls) => {
-            if urls.contains(
-                &"https://www.sodexo.fi/sites/default/themes/sodexo/images/sodexo-leaf.svg"
-                    .to_string(),
-            ) {
-                food_info.co2 = true;
-            }
-
-            if urls.contains(
-                &"https://www.sodexo.fi/sites/default/themes/sodexo/images/sydan.svg".to_string(),
-            ) {
-                food_info.heart = true;
-            }
-
-            if urls.contains(
-                &"https://www.sodexo.fi/sites/default/themes/sodexo/images/vege.svg".to_string(),
-            ) {
-                food_info.vegan = true;
-            }
+type SetIcon = fn(&mut FoodInfo);
+
+/// Sodexo's diet icons by file stem, `https://www.sodexo.fi/.../images/sydan.svg` is `sydan`
+const DIET_ICONS: &[(&str, SetIcon)] = &[
+    ("sodexo-leaf", |f| f.co2 = true),
+    ("sydan", |f| f.heart = true),
+    ("vege", |f| f.vegan = true),
+    ("omena", |f| f.student_recommendation = true),
+    ("possu", |f| f.pork = true),
+    ("liha-fi-new", |f| f.fi_meat = true),
+    ("liha-fi", |f| f.fi_meat = true),
+    ("liha-eu-new", |f| f.eu_meat = true),
+    ("liha-eu", |f| f.eu_meat = true),
+    ("liha-muu-new", |f| f.other_meat = true),
+    ("liha-muu", |f| f.other_meat = true),
+];
+
+/// The file name of an icon url without its extension, query or directories
+fn icon_stem(url: &str) -> String {
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    let name = path.rsplit('/').next().unwrap_or_default();
+    let stem = name.rsplit_once('.').map_or(name, |(stem, _)| stem);
+
+    stem.to_lowercase()
+}
 
-            if urls.contains(
-                &"https://www.sodexo.fi/sites/default/themes/sodexo/images/omena.svg".to_string(),
-            ) {
-                food_info.student_recommendation = true;
-            }
+impl FoodInfo {
+    pub fn from_icons(urls: &[String]) -> Self {
+        let mut food_info = FoodInfo::default();
 
-            if urls.contains(
-                &"https://www.sodexo.fi/sites/default/themes/sodexo/images/possu.svg".to_string(),
-            ) {
-                food_info.pork = true;
-            }
-
-            if urls.contains(
-                &"https://www.sodexo.fi/sites/default/themes/sodexo/images/liha-fi-new.svg"
-                    .to_string(),
-            ) {
-                food_info.fi_meat = true;
+        for stem in urls.iter().map(|u| icon_stem(u)) {
+            match DIET_ICONS.iter().find(|(s, _)| *s == stem) {
+                Some((_, set)) => set(&mut food_info),
+                None => food_info.unknown.push(stem),
             }
+        }
 
-            if urls.contains(
-                &"https://www.sodexo.fi/sites/default/themes/sodexo/images/liha-eu-new.svg"
-                    .to_string(),
-            ) {
-                food_info.eu_meat = true;
-            }
+        food_info
+    }
+}
 
-            if urls.contains(
-                &"https://www.sodexo.fi/sites/default/themes/sodexo/images/liha-muu-new.svg"
-                    .to_string(),
-            ) {
-                food_info.other_meat = true;
-            }
+fn deserialize_food_info<'de, D>(deserializer: D) -> Result<FoodInfo, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let urls: Option<Vec<String>> = Option::deserialize(deserializer)?;
 
-            Ok(food_info)
-        }
-        None => Ok(food_info),
-    }
+    Ok(FoodInfo::from_icons(&urls.unwrap_or_default()))
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct DietInfo {
-    pub gluten_free: bool,
-    pub lactose_free: bool,
-    pub milk_free: bool,
-    pub low_lactose: bool,
+    pub codes: Vec<DietCode>,
 }
 
 fn deserialize_diet_info<'de, D>(deserializer: D) -> Result<DietInfo, D::Error>
 where
     D: Deserializer<'de>,
 {
-    let mut diet_info = DietInfo::default();
-
     let codes: Option<String> = Option::deserialize(deserializer)?;
 
-    match codes {
-        Some(codes) => {
-            for i in codes.split(',').collect::<Vec<&str>>() {
-                match i {
-                    "G" => diet_info.gluten_free = true,
-                    "L" => diet_info.lactose_free = true,
-                    "M" => diet_info.milk_free = true,
-                    "VL" => diet_info.low_lactose = true,
-                    _ => (),
-                }
-            }
-
-            Ok(diet_info)
-        }
-        None => Ok(diet_info),
-    }
+    Ok(DietInfo {
+        codes: parse_diet_codes(codes.as_deref().unwrap_or_default()),
+    })
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -196,11 +180,17 @@ pub struct StringOrEmptyList {
     inner: Either<String, Vec<()>>,
 }
 
-impl ToString for StringOrEmptyList {
-    fn to_string(&self) -> String {
+impl StringOrEmptyList {
+    pub fn into_option(self) -> Option<String> {
+        self.inner.left()
+    }
+}
+
+impl std::fmt::Display for StringOrEmptyList {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match &self.inner {
-            Left(s) => s.clone(),
-            Right(_) => "N/A".to_string(),
+            Left(s) => f.write_str(s),
+            Right(_) => f.write_str("N/A"),
         }
     }
 }
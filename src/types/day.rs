@@ -1,12 +1,11 @@
-use crate::types::courses_as_hashmap;
+use crate::types::courses_in_order;
 use serde::Deserialize;
-use std::collections::HashMap;
 
 use super::common::{Course, Meta};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct DailyMenu {
     pub meta: Meta,
-    #[serde(deserialize_with = "courses_as_hashmap", default)]
-    pub courses: HashMap<String, Course>,
+    #[serde(deserialize_with = "courses_in_order", default)]
+    pub courses: Vec<(String, Course)>,
 }
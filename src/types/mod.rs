@@ -8,7 +8,8 @@ pub mod day;
 // pub mod diet_info;
 pub mod week;
 
-fn courses_as_hashmap<'de, D>(deserializer: D) -> Result<HashMap<String, Course>, D::Error>
+/// Sodexo numbers the courses `"1"`, `"2"`, ..., they are kept in that order
+fn courses_in_order<'de, D>(deserializer: D) -> Result<Vec<(String, Course)>, D::Error>
 where
     D: Deserializer<'de>,
 {
@@ -21,8 +22,8 @@ where
     }
 
     match Helper::deserialize(deserializer)? {
-        Helper::Map(map) => Ok(map),
-        Helper::List(_) => Ok(HashMap::new()), // discard list, return empty
+        Helper::Map(map) => Ok(common::sorted_by_key(map)),
+        Helper::List(_) => Ok(Vec::new()), // discard list, return empty
     }
 }
 
@@ -61,11 +62,31 @@ fn additional_diet_info() {
 				"allergens": "Chili, Herneet, Hiivauute, Kaura, Korianteri, Rikkidioksidi ja sulfiitit, Sipuli, Sitrukset, Soijapavut, Valkosipuli"
 }"#;
 
-    let _: AdditionalDietInfo = serde_json::from_str(json).expect("unable to parse json");
+    let info: AdditionalDietInfo = serde_json::from_str(json).expect("unable to parse json");
+    let food = info.food_info;
+    assert!(food.co2 && food.heart && food.vegan && food.student_recommendation);
+    assert!(!food.pork && food.unknown.is_empty());
+
     let _: AdditionalDietInfo = serde_json::from_str(json2).expect("unable to parse json");
     let _: AdditionalDietInfo = serde_json::from_str(json3).expect("unable to parse json");
 }
 
+#[test]
+fn diet_icons_by_stem() {
+    use crate::types::common::FoodInfo;
+
+    let food = FoodInfo::from_icons(&[
+        "https://cdn.sodexo.fi/themes/sodexo/images/possu.svg?v=2".to_string(),
+        "/images/LIHA-FI-NEW.png".to_string(),
+        "liha-eu.svg".to_string(),
+        "https://www.sodexo.fi/sites/default/themes/sodexo/images/kala-msc.svg".to_string(),
+    ]);
+
+    assert!(food.pork && food.fi_meat && food.eu_meat);
+    assert!(!food.other_meat);
+    assert_eq!(food.unknown, vec!["kala-msc".to_string()]);
+}
+
 #[test]
 fn deserialize_empty_day() {
     use crate::types::day::DailyMenu;
@@ -95,3 +116,36 @@ fn test_deserialize_weekly_menu() {
 
     println!("{:#?}", menu);
 }
+
+#[test]
+fn courses_keep_their_order() {
+    use crate::types::week::WeeklyMenu;
+
+    let weekly = std::fs::read_to_string("tests/weekly.json").expect("no file");
+    let menu: WeeklyMenu = serde_json::from_str(&weekly).expect("unable to parse json");
+
+    for day in menu.mealdates {
+        let keys: Vec<u32> = day
+            .courses
+            .iter()
+            .map(|(k, _)| k.parse().expect("non numeric key"))
+            .collect();
+
+        assert!(keys.is_sorted(), "{keys:?}");
+    }
+}
+
+#[test]
+fn sorted_by_numeric_key() {
+    let map: HashMap<String, ()> = ["10", "2", "1", "hideAll"]
+        .into_iter()
+        .map(|k| (k.to_string(), ()))
+        .collect();
+
+    let keys: Vec<String> = common::sorted_by_key(map)
+        .into_iter()
+        .map(|(k, _)| k)
+        .collect();
+
+    assert_eq!(keys, vec!["1", "2", "10", "hideAll"]);
+}